@@ -5,9 +5,25 @@
 use wasm_bindgen::prelude::*;
 
 // Re-export all WASM functions from the core library
-pub use eda_core::config::{get_kafka_broker, get_kafka_group, get_kafka_topic};
-pub use eda_core::retry::{calculate_backoff_wasm as calculate_backoff, should_retry_wasm as should_retry};
-pub use eda_core::routing::route_event_wasm as route_event;
+pub use eda_core::config::{
+    add_cluster_wasm as add_cluster, get_kafka_broker, get_kafka_group, get_kafka_topic, list_clusters_wasm as list_clusters,
+};
+pub use eda_core::dlq::{
+    dlq_clear_wasm as dlq_clear, dlq_list_wasm as dlq_list, dlq_push_wasm as dlq_push, dlq_replay_wasm as dlq_replay,
+};
+pub use eda_core::retry::{
+    add_error_rule_wasm as add_error_rule, calculate_backoff_wasm as calculate_backoff,
+    calculate_backoff_with_seed_wasm as calculate_backoff_with_seed, clear_error_rules_wasm as clear_error_rules,
+    configure_backoff_wasm as configure_backoff, is_circuit_open_wasm as is_circuit_open,
+    record_destination_outcome_wasm as record_destination_outcome, should_retry_for_destination_wasm as should_retry_for_destination,
+    should_retry_wasm as should_retry,
+};
+pub use eda_core::routing::{
+    add_subscription_wasm as add_subscription, get_output_destination_options_wasm as get_output_destination_options,
+    match_handlers_wasm as match_handlers, record_replica_outcome_wasm as record_replica_outcome,
+    register_replica_pool_wasm as register_replica_pool, set_replica_health_wasm as set_replica_health,
+};
+pub use eda_core::telemetry::{gather_metrics_wasm as gather_metrics, get_outgoing_traceparent_wasm as get_outgoing_traceparent};
 
 /// Initialize the WASM module (called automatically on load)
 #[wasm_bindgen(start)]
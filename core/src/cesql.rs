@@ -0,0 +1,548 @@
+//! CloudEvents SQL (CESQL) expression dialect for routing filters.
+//!
+//! Implements a small subset of the CESQL grammar described at
+//! https://github.com/cloudevents/spec/blob/main/cesql/spec.md: a
+//! tokenizer, a precedence-climbing parser building an AST, and an
+//! evaluator that resolves identifiers against an `Event` via
+//! `routing::get_event_attribute`.
+//!
+//! Supported: comparison operators (`= != < <= > >=`), boolean operators
+//! (`AND OR NOT XOR`), `LIKE` with `%`/`_` wildcards, `IN (...)`,
+//! `EXISTS <attr>`, string/number literals, and the functions `LENGTH`,
+//! `CONCAT`, `LOWER`, `UPPER`, `SUBSTRING`.
+//!
+//! Per CESQL semantics, referencing an absent attribute yields the
+//! target type's default (empty string / 0 / false) rather than an
+//! error, except under `EXISTS`. Comparisons coerce operands across
+//! string/number/bool as needed, and the top-level expression must
+//! coerce to Boolean.
+
+use cloudevents::Event;
+
+use crate::filter_value::{compare, scan_number_literal, scan_string_literal, ScalarValue};
+use crate::routing::{event_attribute_exists, get_event_attribute};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    And,
+    Or,
+    Not,
+    Xor,
+    Like,
+    In,
+    Exists,
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '\'' | '"' => {
+                tokens.push(Token::Str(scan_string_literal(&chars, &mut i)?));
+            }
+            '=' => {
+                tokens.push(Token::Op("="));
+                i += 1;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op("!="));
+                i += 2;
+            }
+            '<' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op("<="));
+                i += 2;
+            }
+            '<' => {
+                tokens.push(Token::Op("<"));
+                i += 1;
+            }
+            '>' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op(">="));
+                i += 2;
+            }
+            '>' => {
+                tokens.push(Token::Op(">"));
+                i += 1;
+            }
+            _ if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) => {
+                tokens.push(Token::Num(scan_number_literal(&chars, &mut i)?));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                match word.to_uppercase().as_str() {
+                    "AND" => tokens.push(Token::And),
+                    "OR" => tokens.push(Token::Or),
+                    "NOT" => tokens.push(Token::Not),
+                    "XOR" => tokens.push(Token::Xor),
+                    "LIKE" => tokens.push(Token::Like),
+                    "IN" => tokens.push(Token::In),
+                    "EXISTS" => tokens.push(Token::Exists),
+                    "TRUE" => tokens.push(Token::Ident("TRUE".to_string())),
+                    "FALSE" => tokens.push(Token::Ident("FALSE".to_string())),
+                    _ => tokens.push(Token::Ident(word)),
+                }
+            }
+            _ => return Err(format!("unexpected character: {}", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cmp(&'static str, Box<Expr>, Box<Expr>),
+    Like(Box<Expr>, String),
+    In(Box<Expr>, Vec<Expr>),
+    Exists(String),
+    Func(String, Vec<Expr>),
+}
+
+/// Recursive-descent / precedence-climbing parser.
+///
+/// Precedence, lowest to highest: `OR` < `XOR` < `AND` < `NOT` < comparison
+/// (`= != < <= > >= LIKE IN`) < primary.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let t = self.tokens.get(self.pos);
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, tok: &Token) -> Result<(), String> {
+        match self.advance() {
+            Some(t) if t == tok => Ok(()),
+            Some(t) => Err(format!("expected {:?}, found {:?}", tok, t)),
+            None => Err(format!("expected {:?}, found end of input", tok)),
+        }
+    }
+
+    fn parse(&mut self) -> Result<Expr, String> {
+        let expr = self.parse_or()?;
+        if self.pos != self.tokens.len() {
+            return Err(format!("unexpected trailing token: {:?}", self.peek()));
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_xor()?;
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.advance();
+            let rhs = self.parse_xor()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_xor(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Xor)) {
+            self.advance();
+            let rhs = self.parse_and()?;
+            lhs = Expr::Xor(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_not()?;
+        while matches!(self.peek(), Some(Token::And)) {
+            self.advance();
+            let rhs = self.parse_not()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        if matches!(self.peek(), Some(Token::Not)) {
+            self.advance();
+            let inner = self.parse_not()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        if matches!(self.peek(), Some(Token::Exists)) {
+            self.advance();
+            match self.advance() {
+                Some(Token::Ident(name)) => return Ok(Expr::Exists(name.clone())),
+                other => return Err(format!("expected identifier after EXISTS, found {:?}", other)),
+            }
+        }
+
+        let lhs = self.parse_primary()?;
+
+        match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.advance();
+                let rhs = self.parse_primary()?;
+                Ok(Expr::Cmp(op, Box::new(lhs), Box::new(rhs)))
+            }
+            Some(Token::Like) => {
+                self.advance();
+                match self.advance() {
+                    Some(Token::Str(pattern)) => Ok(Expr::Like(Box::new(lhs), pattern.clone())),
+                    other => Err(format!("expected string pattern after LIKE, found {:?}", other)),
+                }
+            }
+            Some(Token::In) => {
+                self.advance();
+                self.expect(&Token::LParen)?;
+                let mut items = Vec::new();
+                if !matches!(self.peek(), Some(Token::RParen)) {
+                    loop {
+                        items.push(self.parse_primary()?);
+                        if matches!(self.peek(), Some(Token::Comma)) {
+                            self.advance();
+                            continue;
+                        }
+                        break;
+                    }
+                }
+                self.expect(&Token::RParen)?;
+                Ok(Expr::In(Box::new(lhs), items))
+            }
+            _ => Ok(lhs),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.advance() {
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                self.expect(&Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Str(s)) => Ok(Expr::Str(s.clone())),
+            Some(Token::Num(n)) => Ok(Expr::Num(*n)),
+            Some(Token::Ident(name)) if name == "TRUE" => Ok(Expr::Bool(true)),
+            Some(Token::Ident(name)) if name == "FALSE" => Ok(Expr::Bool(false)),
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                if matches!(self.peek(), Some(Token::LParen)) && is_known_function(&name) {
+                    self.advance();
+                    let mut args = Vec::new();
+                    if !matches!(self.peek(), Some(Token::RParen)) {
+                        loop {
+                            args.push(self.parse_or()?);
+                            if matches!(self.peek(), Some(Token::Comma)) {
+                                self.advance();
+                                continue;
+                            }
+                            break;
+                        }
+                    }
+                    self.expect(&Token::RParen)?;
+                    Ok(Expr::Func(name, args))
+                } else {
+                    Ok(Expr::Ident(name))
+                }
+            }
+            other => Err(format!("unexpected token: {:?}", other)),
+        }
+    }
+}
+
+fn is_known_function(name: &str) -> bool {
+    matches!(
+        name.to_uppercase().as_str(),
+        "LENGTH" | "CONCAT" | "LOWER" | "UPPER" | "SUBSTRING"
+    )
+}
+
+fn attribute_value(event: &Event, name: &str) -> ScalarValue {
+    ScalarValue::Str(get_event_attribute(event, name))
+}
+
+fn eval(event: &Event, expr: &Expr) -> Result<ScalarValue, String> {
+    match expr {
+        Expr::Ident(name) => Ok(attribute_value(event, name)),
+        Expr::Str(s) => Ok(ScalarValue::Str(s.clone())),
+        Expr::Num(n) => Ok(ScalarValue::Num(*n)),
+        Expr::Bool(b) => Ok(ScalarValue::Bool(*b)),
+        Expr::And(l, r) => Ok(ScalarValue::Bool(eval(event, l)?.to_bool(true) && eval(event, r)?.to_bool(true))),
+        Expr::Or(l, r) => Ok(ScalarValue::Bool(eval(event, l)?.to_bool(true) || eval(event, r)?.to_bool(true))),
+        Expr::Xor(l, r) => Ok(ScalarValue::Bool(eval(event, l)?.to_bool(true) ^ eval(event, r)?.to_bool(true))),
+        Expr::Not(e) => Ok(ScalarValue::Bool(!eval(event, e)?.to_bool(true))),
+        Expr::Exists(name) => Ok(ScalarValue::Bool(event_attribute_exists(event, name))),
+        Expr::Cmp(op, l, r) => {
+            let lv = eval(event, l)?;
+            let rv = eval(event, r)?;
+            Ok(ScalarValue::Bool(compare(op, &lv, &rv, true)))
+        }
+        Expr::Like(e, pattern) => {
+            let value = eval(event, e)?.to_str();
+            Ok(ScalarValue::Bool(like_match(&value, pattern)))
+        }
+        Expr::In(e, items) => {
+            let value = eval(event, e)?;
+            for item in items {
+                let iv = eval(event, item)?;
+                if compare("=", &value, &iv, true) {
+                    return Ok(ScalarValue::Bool(true));
+                }
+            }
+            Ok(ScalarValue::Bool(false))
+        }
+        Expr::Func(name, args) => eval_func(event, name, args),
+    }
+}
+
+fn like_match(value: &str, pattern: &str) -> bool {
+    // Translate the SQL `%`/`_` wildcards into an anchored regex-free match
+    // by splitting on `%` and checking each literal segment in order, with
+    // `_` matching any single character within a segment.
+    fn segment_matches(value: &str, segment: &str) -> Option<usize> {
+        // Returns the number of chars consumed from `value` if `segment`
+        // matches starting at position 0, honoring `_` as a single-char
+        // wildcard. Operates on chars to stay correct for non-ASCII input.
+        let vchars: Vec<char> = value.chars().collect();
+        let schars: Vec<char> = segment.chars().collect();
+        if vchars.len() < schars.len() {
+            return None;
+        }
+        for (i, sc) in schars.iter().enumerate() {
+            if *sc != '_' && *sc != vchars[i] {
+                return None;
+            }
+        }
+        Some(schars.len())
+    }
+
+    let segments: Vec<&str> = pattern.split('%').collect();
+    if segments.len() == 1 {
+        return value.chars().count() == segments[0].chars().count()
+            && segment_matches(value, segments[0]).is_some();
+    }
+
+    let value_chars: Vec<char> = value.chars().collect();
+    let mut pos = 0usize;
+    for (idx, seg) in segments.iter().enumerate() {
+        if seg.is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            match segment_matches(&value_chars[pos..].iter().collect::<String>(), seg) {
+                Some(len) => pos += len,
+                None => return false,
+            }
+        } else if idx == segments.len() - 1 {
+            let seg_chars: Vec<char> = seg.chars().collect();
+            if value_chars.len() < pos || value_chars.len() - pos < seg_chars.len() {
+                return false;
+            }
+            let tail_start = value_chars.len() - seg_chars.len();
+            if tail_start < pos {
+                return false;
+            }
+            let tail: String = value_chars[tail_start..].iter().collect();
+            if segment_matches(&tail, seg).is_none() {
+                return false;
+            }
+            pos = value_chars.len();
+        } else {
+            let remaining: String = value_chars[pos..].iter().collect();
+            let seg_len = seg.chars().count();
+            let mut found = false;
+            for start in 0..=remaining.chars().count().saturating_sub(seg_len) {
+                let window: String = remaining.chars().skip(start).take(seg_len).collect();
+                if segment_matches(&window, seg).is_some() {
+                    pos += start + seg_len;
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn eval_func(event: &Event, name: &str, args: &[Expr]) -> Result<ScalarValue, String> {
+    let values: Result<Vec<ScalarValue>, String> = args.iter().map(|a| eval(event, a)).collect();
+    let values = values?;
+
+    match name.to_uppercase().as_str() {
+        "LENGTH" => {
+            let s = values.first().map(|v| v.to_str()).unwrap_or_default();
+            Ok(ScalarValue::Num(s.chars().count() as f64))
+        }
+        "CONCAT" => Ok(ScalarValue::Str(values.iter().map(|v| v.to_str()).collect())),
+        "LOWER" => Ok(ScalarValue::Str(
+            values.first().map(|v| v.to_str().to_lowercase()).unwrap_or_default(),
+        )),
+        "UPPER" => Ok(ScalarValue::Str(
+            values.first().map(|v| v.to_str().to_uppercase()).unwrap_or_default(),
+        )),
+        "SUBSTRING" => {
+            let s = values.first().map(|v| v.to_str()).unwrap_or_default();
+            let start = values.get(1).map(|v| v.to_num() as usize).unwrap_or(1).max(1) - 1;
+            let chars: Vec<char> = s.chars().collect();
+            let result = match values.get(2).map(|v| v.to_num() as usize) {
+                Some(len) => chars.iter().skip(start).take(len).collect(),
+                None => chars.iter().skip(start).collect(),
+            };
+            Ok(ScalarValue::Str(result))
+        }
+        _ => Err(format!("unknown function: {}", name)),
+    }
+}
+
+/// Parse and evaluate a CESQL expression against an `Event`, coercing the
+/// top-level result to Boolean. Returns `Err` on a parse error so callers
+/// can decide how to treat an invalid rule (the routing filter dialect
+/// treats it as non-matching).
+pub fn evaluate(event: &Event, expr: &str) -> Result<bool, String> {
+    let tokens = tokenize(expr)?;
+    let ast = Parser::new(&tokens).parse()?;
+    Ok(eval(event, &ast)?.to_bool(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cloudevents::EventBuilder;
+    use cloudevents::EventBuilderV10;
+
+    fn test_event() -> Event {
+        EventBuilderV10::new()
+            .id("1")
+            .ty("com.example.order.created")
+            .source("test")
+            .extension("priority", "high")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_like_and_exists() {
+        let event = test_event();
+        assert!(evaluate(&event, "type LIKE 'com.example.%' AND EXISTS priority").unwrap());
+        assert!(!evaluate(&event, "type LIKE 'com.other.%' AND EXISTS priority").unwrap());
+        assert!(!evaluate(&event, "type LIKE 'com.example.%' AND EXISTS missing").unwrap());
+    }
+
+    #[test]
+    fn test_comparison_and_in() {
+        let event = test_event();
+        assert!(evaluate(&event, "type = 'com.example.order.created'").unwrap());
+        assert!(evaluate(&event, "type != 'com.example.other'").unwrap());
+        assert!(evaluate(&event, "priority IN ('low', 'high')").unwrap());
+        assert!(!evaluate(&event, "priority IN ('low', 'medium')").unwrap());
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        let event = test_event();
+        assert!(evaluate(&event, "NOT (type = 'nope')").unwrap());
+        assert!(evaluate(&event, "(type = 'nope') XOR (priority = 'high')").unwrap());
+        assert!(!evaluate(&event, "(type = 'nope') XOR (priority = 'low')").unwrap());
+    }
+
+    #[test]
+    fn test_functions() {
+        let event = test_event();
+        assert!(evaluate(&event, "LENGTH(priority) = 4").unwrap());
+        assert!(evaluate(&event, "LOWER(priority) = 'high'").unwrap());
+        assert!(evaluate(&event, "UPPER(priority) = 'HIGH'").unwrap());
+        assert!(evaluate(&event, "CONCAT(priority, '-x') = 'high-x'").unwrap());
+        assert!(evaluate(&event, "SUBSTRING(priority, 1, 2) = 'hi'").unwrap());
+    }
+
+    #[test]
+    fn test_string_literal_false_and_zero_are_falsy() {
+        // CESQL's own quirk, distinct from content_filter: the bare strings
+        // "false"/"0" coerce to boolean false, not just the empty string.
+        let event = EventBuilderV10::new()
+            .id("2")
+            .ty("com.example.order.created")
+            .source("test")
+            .extension("flag", "false")
+            .build()
+            .unwrap();
+        assert!(!evaluate(&event, "flag").unwrap());
+        assert!(evaluate(&event, "NOT flag").unwrap());
+
+        let event = EventBuilderV10::new()
+            .id("3")
+            .ty("com.example.order.created")
+            .source("test")
+            .extension("flag", "0")
+            .build()
+            .unwrap();
+        assert!(!evaluate(&event, "flag").unwrap());
+        assert!(evaluate(&event, "NOT flag").unwrap());
+    }
+
+    #[test]
+    fn test_absent_attribute_defaults_to_empty_string() {
+        let event = test_event();
+        assert!(evaluate(&event, "missing = ''").unwrap());
+        assert!(!evaluate(&event, "EXISTS missing").unwrap());
+    }
+
+    #[test]
+    fn test_parse_error_on_malformed_expression() {
+        let event = test_event();
+        assert!(evaluate(&event, "type = ").is_err());
+    }
+}
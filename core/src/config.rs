@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::ffi::CString;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
+use std::sync::RwLock;
 
 /// Kafka configuration for EDA consumers
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +27,33 @@ pub fn get_kafka_config() -> KafkaConfig {
     KafkaConfig::default()
 }
 
+/// Registry of named Kafka clusters, so a routing destination's `cluster`
+/// field (see `routing::OutputDestination`) can resolve to distinct broker
+/// coordinates instead of always hitting the single default cluster.
+static CLUSTERS: RwLock<Option<HashMap<String, KafkaConfig>>> = RwLock::new(None);
+
+/// Register a named Kafka cluster. Overwrites any existing registration
+/// under the same name.
+pub fn add_cluster(name: impl Into<String>, config: KafkaConfig) {
+    CLUSTERS.write().unwrap().get_or_insert_with(HashMap::new).insert(name.into(), config);
+}
+
+/// Look up a cluster registered via `add_cluster`.
+pub fn get_cluster(name: &str) -> Option<KafkaConfig> {
+    CLUSTERS.read().unwrap().as_ref().and_then(|clusters| clusters.get(name).cloned())
+}
+
+/// Names of all registered clusters, in no particular order.
+pub fn list_clusters() -> Vec<String> {
+    CLUSTERS.read().unwrap().as_ref().map(|clusters| clusters.keys().cloned().collect()).unwrap_or_default()
+}
+
+/// Resolve Kafka configuration for `cluster`, falling back to the default
+/// configuration if `cluster` is `None` or isn't registered.
+pub fn get_kafka_config_for(cluster: Option<&str>) -> KafkaConfig {
+    cluster.and_then(get_cluster).unwrap_or_else(get_kafka_config)
+}
+
 /// FFI-compatible function to get Kafka broker
 /// Returns a C string that must be freed by the caller
 #[no_mangle]
@@ -58,6 +87,85 @@ pub extern "C" fn eda_get_kafka_group() -> *mut c_char {
     }
 }
 
+/// Register a named Kafka cluster via FFI.
+#[no_mangle]
+pub extern "C" fn eda_add_cluster(
+    name: *const c_char,
+    broker: *const c_char,
+    topic: *const c_char,
+    group: *const c_char,
+) -> bool {
+    if name.is_null() || broker.is_null() || topic.is_null() || group.is_null() {
+        return false;
+    }
+
+    let (name, broker, topic, group) = unsafe {
+        match (
+            CStr::from_ptr(name).to_str(),
+            CStr::from_ptr(broker).to_str(),
+            CStr::from_ptr(topic).to_str(),
+            CStr::from_ptr(group).to_str(),
+        ) {
+            (Ok(n), Ok(b), Ok(t), Ok(g)) => (n.to_string(), b.to_string(), t.to_string(), g.to_string()),
+            _ => return false,
+        }
+    };
+
+    add_cluster(name, KafkaConfig { broker, topic, group });
+    true
+}
+
+/// FFI-compatible function to get the Kafka broker for a named cluster, or
+/// the default broker if `cluster` is null or unregistered. Returns a C
+/// string that must be freed by the caller.
+#[no_mangle]
+pub extern "C" fn eda_get_kafka_broker_for_cluster(cluster: *const c_char) -> *mut c_char {
+    let config = get_kafka_config_for(cluster_arg(cluster).as_deref());
+    match CString::new(config.broker) {
+        Ok(c_str) => c_str.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI-compatible function to get the Kafka topic for a named cluster, or
+/// the default topic if `cluster` is null or unregistered. Returns a C
+/// string that must be freed by the caller.
+#[no_mangle]
+pub extern "C" fn eda_get_kafka_topic_for_cluster(cluster: *const c_char) -> *mut c_char {
+    let config = get_kafka_config_for(cluster_arg(cluster).as_deref());
+    match CString::new(config.topic) {
+        Ok(c_str) => c_str.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI-compatible function to get the Kafka consumer group for a named
+/// cluster, or the default group if `cluster` is null or unregistered.
+/// Returns a C string that must be freed by the caller.
+#[no_mangle]
+pub extern "C" fn eda_get_kafka_group_for_cluster(cluster: *const c_char) -> *mut c_char {
+    let config = get_kafka_config_for(cluster_arg(cluster).as_deref());
+    match CString::new(config.group) {
+        Ok(c_str) => c_str.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Names of all registered clusters as a JSON array string, to be freed
+/// with `eda_free_string`.
+#[no_mangle]
+pub extern "C" fn eda_list_clusters() -> *mut c_char {
+    let serialized = serde_json::to_string(&list_clusters()).unwrap_or_else(|_| "[]".to_string());
+    CString::new(serialized).map(CString::into_raw).unwrap_or(std::ptr::null_mut())
+}
+
+fn cluster_arg(cluster: *const c_char) -> Option<String> {
+    if cluster.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(cluster).to_str().ok().map(|s| s.to_string()) }
+}
+
 /// FFI-compatible function to free C strings returned by this library
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
@@ -72,20 +180,82 @@ pub extern "C" fn eda_free_string(s: *mut c_char) {
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
+/// Kafka broker for `cluster`, or the default broker if `cluster` is
+/// `None`/unregistered.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn get_kafka_broker(cluster: Option<String>) -> String {
+    get_kafka_config_for(cluster.as_deref()).broker
+}
+
+/// Kafka topic for `cluster`, or the default topic if `cluster` is
+/// `None`/unregistered.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn get_kafka_topic(cluster: Option<String>) -> String {
+    get_kafka_config_for(cluster.as_deref()).topic
+}
+
+/// Kafka consumer group for `cluster`, or the default group if `cluster`
+/// is `None`/unregistered.
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
-pub fn get_kafka_broker() -> String {
-    get_kafka_config().broker
+pub fn get_kafka_group(cluster: Option<String>) -> String {
+    get_kafka_config_for(cluster.as_deref()).group
 }
 
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
-pub fn get_kafka_topic() -> String {
-    get_kafka_config().topic
+pub fn add_cluster_wasm(name: &str, broker: &str, topic: &str, group: &str) {
+    add_cluster(
+        name.to_string(),
+        KafkaConfig {
+            broker: broker.to_string(),
+            topic: topic.to_string(),
+            group: group.to_string(),
+        },
+    );
 }
 
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
-pub fn get_kafka_group() -> String {
-    get_kafka_config().group
+pub fn list_clusters_wasm() -> Vec<String> {
+    list_clusters()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_kafka_config_for_falls_back_to_default() {
+        assert_eq!(get_kafka_config_for(None).broker, get_kafka_config().broker);
+        assert_eq!(get_kafka_config_for(Some("definitely-unregistered-cluster")).broker, get_kafka_config().broker);
+    }
+
+    #[test]
+    fn test_get_cluster_and_get_kafka_config_for_resolve_a_registration() {
+        add_cluster(
+            "chunk1-5-test-cluster",
+            KafkaConfig {
+                broker: "cluster-broker:9092".to_string(),
+                topic: "cluster-topic".to_string(),
+                group: "cluster-group".to_string(),
+            },
+        );
+
+        let cluster = get_cluster("chunk1-5-test-cluster").unwrap();
+        assert_eq!(cluster.broker, "cluster-broker:9092");
+
+        let resolved = get_kafka_config_for(Some("chunk1-5-test-cluster"));
+        assert_eq!(resolved.broker, "cluster-broker:9092");
+        assert_eq!(resolved.topic, "cluster-topic");
+        assert_eq!(resolved.group, "cluster-group");
+    }
+
+    #[test]
+    fn test_list_clusters_reflects_registrations() {
+        add_cluster("chunk1-5-listed-cluster", KafkaConfig::default());
+        assert!(list_clusters().contains(&"chunk1-5-listed-cluster".to_string()));
+    }
 }
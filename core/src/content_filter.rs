@@ -0,0 +1,442 @@
+//! Content-based routing filter expression language, evaluated directly
+//! against an event's JSON representation.
+//!
+//! Supports dotted path access into nested objects (`data.customer.tier`),
+//! the comparison operators `== != < <= > >=`, `in`/`not in` against
+//! literal arrays, boolean `&& || !`, and `exists(path)` checks. A
+//! tokenizer, a precedence-climbing parser building an AST, and an
+//! evaluator that resolves paths against a `serde_json::Value`.
+//!
+//! Unlike `cesql` (which evaluates against CloudEvents attributes via
+//! `routing::get_event_attribute`), this dialect reaches into the full
+//! event JSON, including `data` and any other top-level fields.
+//!
+//! Callers that evaluate the same expression repeatedly (e.g. per-rule
+//! routing filters) should `compile` once and reuse the `CompiledExpr`,
+//! rather than re-tokenizing and re-parsing on every event.
+
+use serde_json::Value;
+
+use crate::filter_value::{compare, scan_number_literal, scan_string_literal, ScalarValue};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    And,
+    Or,
+    Not,
+    In,
+    NotKw,
+    Exists,
+    Op(&'static str),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '[' => {
+                tokens.push(Token::LBracket);
+                i += 1;
+            }
+            ']' => {
+                tokens.push(Token::RBracket);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '\'' | '"' => {
+                tokens.push(Token::Str(scan_string_literal(&chars, &mut i)?));
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op("!="));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op("=="));
+                i += 2;
+            }
+            '<' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op("<="));
+                i += 2;
+            }
+            '<' => {
+                tokens.push(Token::Op("<"));
+                i += 1;
+            }
+            '>' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op(">="));
+                i += 2;
+            }
+            '>' => {
+                tokens.push(Token::Op(">"));
+                i += 1;
+            }
+            _ if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) => {
+                tokens.push(Token::Num(scan_number_literal(&chars, &mut i)?));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                match word.as_str() {
+                    "true" => tokens.push(Token::Bool(true)),
+                    "false" => tokens.push(Token::Bool(false)),
+                    "in" => tokens.push(Token::In),
+                    "not" => tokens.push(Token::NotKw),
+                    "exists" => tokens.push(Token::Exists),
+                    _ => tokens.push(Token::Ident(word)),
+                }
+            }
+            _ => return Err(format!("unexpected character: {}", c)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Path(String),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cmp(&'static str, Box<Expr>, Box<Expr>),
+    /// `In(lhs, items, negate)` — `negate` is `true` for `not in`.
+    In(Box<Expr>, Vec<Expr>, bool),
+    Exists(String),
+}
+
+/// Recursive-descent / precedence-climbing parser.
+///
+/// Precedence, lowest to highest: `||` < `&&` < `!` < comparison
+/// (`== != < <= > >=`, `in`/`not in`) < primary.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let t = self.tokens.get(self.pos);
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, tok: &Token) -> Result<(), String> {
+        match self.advance() {
+            Some(t) if t == tok => Ok(()),
+            Some(t) => Err(format!("expected {:?}, found {:?}", tok, t)),
+            None => Err(format!("expected {:?}, found end of input", tok)),
+        }
+    }
+
+    fn parse(&mut self) -> Result<Expr, String> {
+        let expr = self.parse_or()?;
+        if self.pos != self.tokens.len() {
+            return Err(format!("unexpected trailing token: {:?}", self.peek()));
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.advance();
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_not()?;
+        while matches!(self.peek(), Some(Token::And)) {
+            self.advance();
+            let rhs = self.parse_not()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        if matches!(self.peek(), Some(Token::Not)) {
+            self.advance();
+            let inner = self.parse_not()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        if matches!(self.peek(), Some(Token::Exists)) {
+            self.advance();
+            self.expect(&Token::LParen)?;
+            let path = match self.advance() {
+                Some(Token::Ident(name)) => name.clone(),
+                other => return Err(format!("expected path inside exists(...), found {:?}", other)),
+            };
+            self.expect(&Token::RParen)?;
+            return Ok(Expr::Exists(path));
+        }
+
+        let lhs = self.parse_primary()?;
+
+        match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.advance();
+                let rhs = self.parse_primary()?;
+                Ok(Expr::Cmp(op, Box::new(lhs), Box::new(rhs)))
+            }
+            Some(Token::In) => {
+                self.advance();
+                let items = self.parse_array()?;
+                Ok(Expr::In(Box::new(lhs), items, false))
+            }
+            Some(Token::NotKw) => {
+                self.advance();
+                self.expect(&Token::In)?;
+                let items = self.parse_array()?;
+                Ok(Expr::In(Box::new(lhs), items, true))
+            }
+            _ => Ok(lhs),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Vec<Expr>, String> {
+        self.expect(&Token::LBracket)?;
+        let mut items = Vec::new();
+        if !matches!(self.peek(), Some(Token::RBracket)) {
+            loop {
+                items.push(self.parse_primary()?);
+                if matches!(self.peek(), Some(Token::Comma)) {
+                    self.advance();
+                    continue;
+                }
+                break;
+            }
+        }
+        self.expect(&Token::RBracket)?;
+        Ok(items)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.advance() {
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                self.expect(&Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Str(s)) => Ok(Expr::Str(s.clone())),
+            Some(Token::Num(n)) => Ok(Expr::Num(*n)),
+            Some(Token::Bool(b)) => Ok(Expr::Bool(*b)),
+            Some(Token::Ident(name)) => Ok(Expr::Path(name.clone())),
+            other => Err(format!("unexpected token: {:?}", other)),
+        }
+    }
+}
+
+fn scalar_from_json(value: &Value) -> ScalarValue {
+    match value {
+        Value::String(s) => ScalarValue::Str(s.clone()),
+        Value::Number(n) => ScalarValue::Num(n.as_f64().unwrap_or(0.0)),
+        Value::Bool(b) => ScalarValue::Bool(*b),
+        Value::Null => ScalarValue::Null,
+        other => ScalarValue::Str(other.to_string()),
+    }
+}
+
+/// Resolve a dotted path (`data.customer.tier`) against the event's root
+/// JSON object, returning `None` if any segment is absent.
+fn resolve_path<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = root;
+    for segment in path.split('.') {
+        current = current.as_object()?.get(segment)?;
+    }
+    Some(current)
+}
+
+fn path_exists(root: &Value, path: &str) -> bool {
+    resolve_path(root, path).is_some()
+}
+
+fn path_value(root: &Value, path: &str) -> ScalarValue {
+    resolve_path(root, path).map(scalar_from_json).unwrap_or(ScalarValue::Null)
+}
+
+fn eval(root: &Value, expr: &Expr) -> ScalarValue {
+    match expr {
+        Expr::Path(path) => path_value(root, path),
+        Expr::Str(s) => ScalarValue::Str(s.clone()),
+        Expr::Num(n) => ScalarValue::Num(*n),
+        Expr::Bool(b) => ScalarValue::Bool(*b),
+        Expr::And(l, r) => ScalarValue::Bool(eval(root, l).to_bool(false) && eval(root, r).to_bool(false)),
+        Expr::Or(l, r) => ScalarValue::Bool(eval(root, l).to_bool(false) || eval(root, r).to_bool(false)),
+        Expr::Not(e) => ScalarValue::Bool(!eval(root, e).to_bool(false)),
+        Expr::Exists(path) => ScalarValue::Bool(path_exists(root, path)),
+        Expr::Cmp(op, l, r) => ScalarValue::Bool(compare(op, &eval(root, l), &eval(root, r), false)),
+        Expr::In(e, items, negate) => {
+            let value = eval(root, e);
+            let found = items.iter().any(|item| compare("==", &value, &eval(root, item), false));
+            ScalarValue::Bool(found != *negate)
+        }
+    }
+}
+
+/// A parsed filter expression, ready to be evaluated against many events
+/// without re-tokenizing or re-parsing.
+#[derive(Debug, Clone)]
+pub struct CompiledExpr(Expr);
+
+/// Parse a filter expression, returning a reusable `CompiledExpr`. Callers
+/// that will evaluate the same expression repeatedly (e.g. a routing
+/// rule's filter) should compile once and cache the result.
+pub fn compile(expr: &str) -> Result<CompiledExpr, String> {
+    let tokens = tokenize(expr)?;
+    let ast = Parser::new(&tokens).parse()?;
+    Ok(CompiledExpr(ast))
+}
+
+impl CompiledExpr {
+    /// Evaluate the compiled expression against an event's root JSON value.
+    pub fn evaluate(&self, root: &Value) -> bool {
+        eval(root, &self.0).to_bool(false)
+    }
+}
+
+/// Parse and evaluate a filter expression in one step. Prefer `compile` +
+/// `CompiledExpr::evaluate` when the same expression is evaluated more
+/// than once.
+pub fn evaluate(root: &Value, expr: &str) -> Result<bool, String> {
+    Ok(compile(expr)?.evaluate(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event() -> Value {
+        json!({
+            "specversion": "1.0",
+            "type": "order.created",
+            "source": "test",
+            "id": "1",
+            "amount": 150,
+            "region": "us",
+            "data": {
+                "customer": {
+                    "tier": "gold"
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_comparison_and_boolean_operators() {
+        let e = event();
+        assert!(evaluate(&e, "type == \"order.created\" && amount > 100").unwrap());
+        assert!(!evaluate(&e, "type == \"order.created\" && amount > 1000").unwrap());
+        assert!(evaluate(&e, "amount < 100 || region == \"us\"").unwrap());
+        assert!(evaluate(&e, "!(amount < 100)").unwrap());
+    }
+
+    #[test]
+    fn test_dotted_path_access() {
+        let e = event();
+        assert!(evaluate(&e, "data.customer.tier == \"gold\"").unwrap());
+        assert!(!evaluate(&e, "data.customer.tier == \"silver\"").unwrap());
+    }
+
+    #[test]
+    fn test_in_and_not_in() {
+        let e = event();
+        assert!(evaluate(&e, "region in [\"us\", \"eu\"]").unwrap());
+        assert!(!evaluate(&e, "region in [\"eu\", \"apac\"]").unwrap());
+        assert!(evaluate(&e, "region not in [\"eu\", \"apac\"]").unwrap());
+    }
+
+    #[test]
+    fn test_non_empty_string_is_truthy_even_if_it_looks_falsy() {
+        // Unlike cesql, content_filter treats any non-empty string as
+        // truthy — it has no notion of "false"/"0" string literals being
+        // falsy, so a path holding the literal string "false" is truthy.
+        let e = json!({"flag": "false"});
+        assert!(evaluate(&e, "flag").unwrap());
+        assert!(!evaluate(&e, "!flag").unwrap());
+    }
+
+    #[test]
+    fn test_exists() {
+        let e = event();
+        assert!(evaluate(&e, "exists(data.customer.tier)").unwrap());
+        assert!(!evaluate(&e, "exists(data.customer.missing)").unwrap());
+    }
+
+    #[test]
+    fn test_compiled_expr_is_reusable() {
+        let e = event();
+        let compiled = compile("amount > 100 && region == \"us\"").unwrap();
+        assert!(compiled.evaluate(&e));
+        assert!(compiled.evaluate(&event()));
+    }
+
+    #[test]
+    fn test_parse_error_on_malformed_expression() {
+        assert!(evaluate(&event(), "amount >").is_err());
+        assert!(evaluate(&event(), "region not").is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! In-component dead-letter queue: tracks, inspects, and replays events
+//! that were routed to `routing::get_dead_letter_destination` (typically
+//! because `retry::get_retry_decision` set `send_to_dlq`).
+//!
+//! Records are held in an in-memory ring buffer bounded by `DLQ_CAPACITY`;
+//! pushing past capacity evicts the oldest record. This is a local
+//! inspection aid for the current component instance, not a durable
+//! store — a host that needs DLQ entries to survive a restart should
+//! persist them itself.
+
+use std::collections::VecDeque;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::{retry, routing, telemetry};
+
+/// Maximum number of dead-lettered records retained before the oldest is
+/// evicted to make room for a new one.
+const DLQ_CAPACITY: usize = 1000;
+
+/// One dead-lettered record.
+#[derive(Debug, Clone, Serialize)]
+pub struct DlqRecord {
+    pub event_json: String,
+    pub error_message: String,
+    pub error_category: retry::ErrorCategory,
+    pub attempt_count: u32,
+    /// Unix epoch milliseconds when the record was pushed.
+    pub timestamp_ms: u64,
+    /// The destination target the event was headed to when it was
+    /// dead-lettered.
+    pub destination_target: String,
+}
+
+static DLQ: RwLock<Option<VecDeque<DlqRecord>>> = RwLock::new(None);
+
+fn with_dlq<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut VecDeque<DlqRecord>) -> R,
+{
+    let mut guard = DLQ.write().unwrap();
+    let deque = guard.get_or_insert_with(VecDeque::new);
+    f(deque)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/// Push a dead-lettered record, classifying `error_message` with the
+/// currently configured retry rules. Evicts the oldest record if the
+/// queue is at capacity. Returns the new record's index (stable until the
+/// next eviction shifts it).
+pub fn dlq_push(
+    event_json: impl Into<String>,
+    error_message: impl Into<String>,
+    attempt_count: u32,
+    destination_target: impl Into<String>,
+) -> usize {
+    let error_message = error_message.into();
+    let error_category = retry::classify_error(&error_message);
+
+    let record = DlqRecord {
+        event_json: event_json.into(),
+        error_message,
+        error_category,
+        attempt_count,
+        timestamp_ms: now_ms(),
+        destination_target: destination_target.into(),
+    };
+
+    with_dlq(|deque| {
+        if deque.len() >= DLQ_CAPACITY {
+            deque.pop_front();
+        }
+        deque.push_back(record);
+        deque.len() - 1
+    })
+}
+
+/// All dead-lettered records, oldest first.
+pub fn dlq_list() -> Vec<DlqRecord> {
+    DLQ.read().unwrap().as_ref().map(|deque| deque.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// Dead-lettered records whose stored `error_category` matches `category`,
+/// oldest first.
+pub fn dlq_list_by_category(category: retry::ErrorCategory) -> Vec<DlqRecord> {
+    dlq_list().into_iter().filter(|record| record.error_category == category).collect()
+}
+
+/// Re-run `retry::classify_error` on the record at `index`'s original
+/// error message, reflecting any classification rules added since it was
+/// pushed. `None` if there's no record at `index`.
+pub fn dlq_reclassify(index: usize) -> Option<retry::ErrorCategory> {
+    let guard = DLQ.read().unwrap();
+    let record = guard.as_ref()?.get(index)?;
+    Some(retry::classify_error(&record.error_message))
+}
+
+/// Resolve the replay destination for the record at `index` by re-running
+/// the current routing rules over its original event JSON (via
+/// `routing::get_output_destination`). Does not remove the record or
+/// perform any I/O — the host is expected to actually deliver the event
+/// and report the outcome back (e.g. via `routing::record_replica_outcome`).
+pub fn dlq_replay(index: usize) -> Result<routing::OutputDestination, String> {
+    let event_json = {
+        let guard = DLQ.read().unwrap();
+        guard.as_ref().and_then(|deque| deque.get(index)).map(|record| record.event_json.clone())
+    };
+
+    match event_json {
+        Some(event_json) => {
+            let destination = routing::get_output_destination(&event_json);
+            telemetry::record_dlq_replay(true);
+            Ok(destination)
+        }
+        None => {
+            telemetry::record_dlq_replay(false);
+            Err(format!("no DLQ record at index {}", index))
+        }
+    }
+}
+
+/// Number of records currently held in the DLQ.
+pub fn len() -> usize {
+    DLQ.read().unwrap().as_ref().map(|deque| deque.len()).unwrap_or(0)
+}
+
+/// Remove all dead-lettered records.
+pub fn dlq_clear() {
+    *DLQ.write().unwrap() = None;
+}
+
+// FFI exports
+
+/// Push a dead-lettered record via FFI. Returns the new record's index, or
+/// `-1` if an argument couldn't be read.
+#[no_mangle]
+pub extern "C" fn eda_dlq_push(
+    event_json: *const c_char,
+    error_message: *const c_char,
+    attempt_count: u32,
+    destination_target: *const c_char,
+) -> i64 {
+    if event_json.is_null() || error_message.is_null() || destination_target.is_null() {
+        return -1;
+    }
+
+    let (event_json, error_message, destination_target) = unsafe {
+        match (
+            CStr::from_ptr(event_json).to_str(),
+            CStr::from_ptr(error_message).to_str(),
+            CStr::from_ptr(destination_target).to_str(),
+        ) {
+            (Ok(e), Ok(m), Ok(d)) => (e.to_string(), m.to_string(), d.to_string()),
+            _ => return -1,
+        }
+    };
+
+    dlq_push(event_json, error_message, attempt_count, destination_target) as i64
+}
+
+/// All dead-lettered records as a JSON array string via FFI, to be freed
+/// with `eda_free_string`.
+#[no_mangle]
+pub extern "C" fn eda_dlq_list() -> *mut c_char {
+    let serialized = serde_json::to_string(&dlq_list()).unwrap_or_else(|_| "[]".to_string());
+    CString::new(serialized).map(CString::into_raw).unwrap_or(std::ptr::null_mut())
+}
+
+fn destination_to_c(destination: routing::OutputDestination) -> routing::COutputDestination {
+    let dest_type = match destination.dest_type {
+        routing::DestinationType::Kafka => 0,
+        routing::DestinationType::RabbitMQ => 1,
+        routing::DestinationType::Http => 2,
+        routing::DestinationType::Discard => 3,
+        routing::DestinationType::DeadLetter => 4,
+        routing::DestinationType::Mqtt => 5,
+        routing::DestinationType::Redis => 6,
+        routing::DestinationType::Sql => 7,
+    };
+
+    let target = CString::new(destination.target).unwrap().into_raw();
+    let cluster = destination
+        .cluster
+        .map(|c| CString::new(c).unwrap().into_raw())
+        .unwrap_or(std::ptr::null_mut());
+    let options = if destination.options.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        CString::new(serde_json::to_string(&destination.options).unwrap_or_default())
+            .unwrap_or_default()
+            .into_raw()
+    };
+
+    routing::COutputDestination {
+        dest_type,
+        target,
+        cluster,
+        options,
+    }
+}
+
+/// Resolve the replay destination for the record at `index` via FFI. Free
+/// with `eda_free_output_destination`. Returns null if there's no record
+/// at `index`.
+#[no_mangle]
+pub extern "C" fn eda_dlq_replay(index: u64) -> *mut routing::COutputDestination {
+    match dlq_replay(index as usize) {
+        Ok(destination) => Box::into_raw(Box::new(destination_to_c(destination))),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Clear all dead-lettered records via FFI.
+#[no_mangle]
+pub extern "C" fn eda_dlq_clear() {
+    dlq_clear();
+}
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn dlq_push_wasm(event_json: &str, error_message: &str, attempt_count: u32, destination_target: &str) -> usize {
+    dlq_push(event_json.to_string(), error_message.to_string(), attempt_count, destination_target.to_string())
+}
+
+/// All dead-lettered records as a JSON array string.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn dlq_list_wasm() -> String {
+    serde_json::to_string(&dlq_list()).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Resolve the replay destination for the record at `index`, as a JSON
+/// object string (`{"target":...,"cluster":...,"options":{...}}`), or an
+/// empty string if there's no record at `index`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn dlq_replay_wasm(index: usize) -> String {
+    match dlq_replay(index) {
+        Ok(destination) => serde_json::to_string(&destination.options)
+            .map(|options| format!(
+                r#"{{"target":"{}","cluster":{},"options":{}}}"#,
+                telemetry::escape_json_string(&destination.target),
+                destination
+                    .cluster
+                    .as_ref()
+                    .map(|c| format!("\"{}\"", telemetry::escape_json_string(c)))
+                    .unwrap_or_else(|| "null".to_string()),
+                options
+            ))
+            .unwrap_or_default(),
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn dlq_clear_wasm() {
+    dlq_clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry::ERROR_RULES_TEST_LOCK;
+
+    #[test]
+    fn test_dlq_push_and_list_round_trip() {
+        let _guard = ERROR_RULES_TEST_LOCK.lock().unwrap();
+        dlq_clear();
+
+        let index = dlq_push(
+            r#"{"specversion":"1.0","type":"order.created","source":"test","id":"1"}"#,
+            "connection reset by peer",
+            3,
+            "orders-topic",
+        );
+
+        let records = dlq_list();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[index].attempt_count, 3);
+        assert_eq!(records[index].destination_target, "orders-topic");
+        assert_eq!(records[index].error_category, retry::ErrorCategory::Transient);
+    }
+
+    #[test]
+    fn test_dlq_list_by_category_filters() {
+        let _guard = ERROR_RULES_TEST_LOCK.lock().unwrap();
+        dlq_clear();
+
+        dlq_push(r#"{"id":"1"}"#, "connection reset by peer", 1, "a");
+        dlq_push(r#"{"id":"2"}"#, "invalid payload schema", 1, "b");
+
+        let transient = dlq_list_by_category(retry::ErrorCategory::Transient);
+        assert_eq!(transient.len(), 1);
+        assert_eq!(transient[0].destination_target, "a");
+    }
+
+    #[test]
+    fn test_dlq_reclassify_reflects_newly_added_rules() {
+        let _guard = ERROR_RULES_TEST_LOCK.lock().unwrap();
+        dlq_clear();
+        retry::clear_error_rules();
+
+        let index = dlq_push(r#"{"id":"1"}"#, "widget exploded", 1, "a");
+        assert_eq!(dlq_reclassify(index), Some(retry::ErrorCategory::Unknown));
+
+        retry::add_error_rule("widget exploded", retry::ErrorCategory::Permanent);
+        assert_eq!(dlq_reclassify(index), Some(retry::ErrorCategory::Permanent));
+
+        retry::clear_error_rules();
+    }
+
+    #[test]
+    fn test_dlq_replay_reuses_current_routing_rules() {
+        let _guard = ERROR_RULES_TEST_LOCK.lock().unwrap();
+        dlq_clear();
+        routing::clear_routing_rules();
+
+        let index = dlq_push(
+            r#"{"specversion":"1.0","type":"order.created","source":"test","id":"1"}"#,
+            "timeout",
+            2,
+            "orders-topic",
+        );
+
+        let destination = dlq_replay(index).unwrap();
+        assert_eq!(destination.target, routing::get_default_destination().target);
+
+        assert!(dlq_replay(index + 1).is_err());
+    }
+
+    #[test]
+    fn test_dlq_ring_buffer_evicts_oldest_past_capacity() {
+        let _guard = ERROR_RULES_TEST_LOCK.lock().unwrap();
+        dlq_clear();
+
+        for i in 0..(DLQ_CAPACITY + 5) {
+            dlq_push(format!("{{\"id\":\"{}\"}}", i), "timeout", 1, "a");
+        }
+
+        let records = dlq_list();
+        assert_eq!(records.len(), DLQ_CAPACITY);
+        assert!(records[0].event_json.contains("\"id\":\"5\""));
+    }
+}
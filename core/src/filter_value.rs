@@ -0,0 +1,128 @@
+//! Lexical scanning and value-coercion helpers shared by the `cesql` and
+//! `content_filter` routing-filter dialects. Both tokenizers scan quoted
+//! string and numeric literals identically, and both evaluators resolve to
+//! a small runtime value that coerces across string/number/bool/null the
+//! same way when comparing operands.
+
+/// Runtime value produced by evaluating a filter sub-expression, or by
+/// resolving an attribute/path that may be absent (`Null`).
+#[derive(Debug, Clone)]
+pub(crate) enum ScalarValue {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    Null,
+}
+
+impl ScalarValue {
+    /// Coerce to bool. The two dialects disagree on string truthiness:
+    /// CESQL treats the literal strings `"false"`/`"0"` as falsy on top of
+    /// emptiness, while content_filter treats any non-empty string as
+    /// truthy. `strict_string_falsy` selects which rule applies (`true` for
+    /// CESQL, `false` for content_filter) so neither dialect's behavior
+    /// changes by sharing this type.
+    pub(crate) fn to_bool(&self, strict_string_falsy: bool) -> bool {
+        match self {
+            ScalarValue::Bool(b) => *b,
+            ScalarValue::Num(n) => *n != 0.0,
+            ScalarValue::Str(s) => {
+                if s.is_empty() {
+                    false
+                } else if strict_string_falsy {
+                    s != "false" && s != "0"
+                } else {
+                    true
+                }
+            }
+            ScalarValue::Null => false,
+        }
+    }
+
+    pub(crate) fn to_num(&self) -> f64 {
+        match self {
+            ScalarValue::Num(n) => *n,
+            ScalarValue::Bool(b) => if *b { 1.0 } else { 0.0 },
+            ScalarValue::Str(s) => s.parse::<f64>().unwrap_or(0.0),
+            ScalarValue::Null => 0.0,
+        }
+    }
+
+    pub(crate) fn to_str(&self) -> String {
+        match self {
+            ScalarValue::Str(s) => s.clone(),
+            ScalarValue::Num(n) => n.to_string(),
+            ScalarValue::Bool(b) => b.to_string(),
+            ScalarValue::Null => String::new(),
+        }
+    }
+}
+
+/// Compare two values, coercing across string/number/bool: numeric if
+/// either side is a number, boolean if either side is a bool, string
+/// otherwise. Accepts both `=` (CESQL) and `==` (content-filter) as the
+/// equality operator so both dialects can share this directly.
+/// `strict_string_falsy` is forwarded to `to_bool` for the bool-coercion
+/// branch — see `ScalarValue::to_bool`.
+pub(crate) fn compare(op: &str, l: &ScalarValue, r: &ScalarValue, strict_string_falsy: bool) -> bool {
+    let both_numeric = matches!(l, ScalarValue::Num(_)) || matches!(r, ScalarValue::Num(_));
+    if both_numeric {
+        let (a, b) = (l.to_num(), r.to_num());
+        return match op {
+            "=" | "==" => a == b,
+            "!=" => a != b,
+            "<" => a < b,
+            "<=" => a <= b,
+            ">" => a > b,
+            ">=" => a >= b,
+            _ => false,
+        };
+    }
+    if matches!(l, ScalarValue::Bool(_)) || matches!(r, ScalarValue::Bool(_)) {
+        let (a, b) = (l.to_bool(strict_string_falsy), r.to_bool(strict_string_falsy));
+        return match op {
+            "=" | "==" => a == b,
+            "!=" => a != b,
+            _ => false,
+        };
+    }
+    let (a, b) = (l.to_str(), r.to_str());
+    match op {
+        "=" | "==" => a == b,
+        "!=" => a != b,
+        "<" => a < b,
+        "<=" => a <= b,
+        ">" => a > b,
+        ">=" => a >= b,
+        _ => false,
+    }
+}
+
+/// Scan a quoted string literal. `chars[*i]` must be the opening quote
+/// (`'` or `"`); advances `*i` past the matching closing quote.
+pub(crate) fn scan_string_literal(chars: &[char], i: &mut usize) -> Result<String, String> {
+    let quote = chars[*i];
+    let mut s = String::new();
+    *i += 1;
+    while *i < chars.len() && chars[*i] != quote {
+        s.push(chars[*i]);
+        *i += 1;
+    }
+    if *i >= chars.len() {
+        return Err("unterminated string literal".to_string());
+    }
+    *i += 1;
+    Ok(s)
+}
+
+/// Scan a numeric literal (digits, an optional leading `-`, and an
+/// optional decimal point). `chars[*i]` must be the leading `-` or first
+/// digit; advances `*i` past the last digit.
+pub(crate) fn scan_number_literal(chars: &[char], i: &mut usize) -> Result<f64, String> {
+    let start = *i;
+    *i += 1;
+    while *i < chars.len() && (chars[*i].is_ascii_digit() || chars[*i] == '.') {
+        *i += 1;
+    }
+    let lexeme: String = chars[start..*i].iter().collect();
+    lexeme.parse::<f64>().map_err(|_| format!("invalid number literal: {}", lexeme))
+}
@@ -2,19 +2,26 @@
 //!
 //! This library provides core functionality for EDA consumers across multiple languages:
 //! - Configuration management (Kafka broker, topic, consumer group)
-//! - Retry logic with exponential backoff
+//! - Retry logic with exponential backoff, jitter, and per-destination circuit breaking
 //! - Event routing to handlers
+//! - Telemetry: Prometheus metrics exposition
 //!
 //! The library is designed to be consumed via FFI (Go, Python, Java) or WASM (JavaScript).
 
+pub mod cesql;
 pub mod config;
+pub mod content_filter;
+pub mod dlq;
+mod filter_value;
 pub mod retry;
 pub mod routing;
+pub mod schema_registry;
+pub mod telemetry;
 
 // Re-export main types for convenience
 pub use config::{get_kafka_config, KafkaConfig};
 pub use retry::{calculate_backoff, should_retry};
-pub use routing::route_event;
+pub use routing::match_handlers;
 
 #[cfg(test)]
 mod tests {
@@ -29,20 +36,26 @@ mod tests {
     }
 
     #[test]
-    fn test_retry_noop() {
-        assert_eq!(should_retry("some error", 1), false);
-        assert_eq!(should_retry("another error", 5), false);
+    fn test_should_retry_classifies_errors() {
+        assert!(should_retry("connection refused", 0));
+        assert!(!should_retry("404 not found", 0));
     }
 
     #[test]
-    fn test_backoff_noop() {
-        assert_eq!(calculate_backoff(1), 0);
-        assert_eq!(calculate_backoff(10), 0);
+    fn test_calculate_backoff_is_bounded() {
+        let backoff = calculate_backoff(1);
+        assert!(backoff <= 30_000);
     }
 
     #[test]
-    fn test_routing_noop() {
-        assert_eq!(route_event("user.created"), 0);
-        assert_eq!(route_event("order.placed"), 0);
+    fn test_match_handlers_dispatches_to_subscribed_handler() {
+        routing::clear_subscriptions();
+        routing::add_subscription(r#"{"exact":{"type":"user.created"}}"#.to_string(), "user-handler".to_string(), 0);
+
+        let event_json = r#"{"specversion":"1.0","type":"user.created","source":"test","id":"1"}"#;
+        assert_eq!(match_handlers(event_json), vec!["user-handler".to_string()]);
+
+        let unrelated_json = r#"{"specversion":"1.0","type":"order.placed","source":"test","id":"2"}"#;
+        assert!(match_handlers(unrelated_json).is_empty());
     }
 }
@@ -1,18 +1,526 @@
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::fs;
 use std::os::raw::c_char;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-/// Determine if an error should be retried (noop for PoC)
-/// Always returns false - no retry logic implemented yet
-pub fn should_retry(_error: &str, _attempt: u32) -> bool {
-    false
+use serde::{Deserialize, Serialize};
+
+use crate::telemetry;
+
+/// How a failure should be treated for retry purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCategory {
+    /// Worth retrying (timeouts, connection resets, 5xx-class errors, ...).
+    Transient,
+    /// Retrying won't help (validation errors, 4xx-class errors, ...).
+    Permanent,
+    /// Didn't match any configured pattern.
+    Unknown,
+}
+
+/// The outcome of evaluating a retry policy for one attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryDecision {
+    pub should_retry: bool,
+    pub backoff_ms: u64,
+    pub send_to_dlq: bool,
+}
+
+/// Shape of the backoff curve used by `calculate_backoff_with_seed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffPolicy {
+    /// Always `base_backoff_ms` (capped at `max_backoff_ms`).
+    Fixed,
+    /// `base * factor^attempt`, capped at `max_backoff_ms`, no jitter.
+    Exponential,
+    /// `rand_uniform(0, min(cap, base * factor^attempt))`.
+    FullJitter,
+    /// `min(cap, rand_uniform(base, prev_sleep * 3))`, where `prev_sleep`
+    /// starts at `base` on the first attempt and is remembered per seed.
+    DecorrelatedJitter,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        BackoffPolicy::FullJitter
+    }
+}
+
+/// Retry policy: backoff shape, attempt cap, and error classification patterns.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub base_backoff_ms: u64,
+    pub backoff_factor: f64,
+    pub max_backoff_ms: u64,
+    pub max_attempts: u32,
+    pub backoff_policy: BackoffPolicy,
+    /// Substrings (case-insensitive) that classify an error as `Permanent`.
+    /// Checked before `retryable_patterns`, so an explicit non-retryable
+    /// match always wins.
+    pub non_retryable_patterns: Vec<String>,
+    /// Substrings (case-insensitive) that classify an error as `Transient`.
+    pub retryable_patterns: Vec<String>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            base_backoff_ms: 100,
+            backoff_factor: 2.0,
+            max_backoff_ms: 30_000,
+            max_attempts: 5,
+            backoff_policy: BackoffPolicy::FullJitter,
+            non_retryable_patterns: vec![
+                "400".to_string(),
+                "401".to_string(),
+                "403".to_string(),
+                "404".to_string(),
+                "invalid".to_string(),
+                "unauthorized".to_string(),
+                "not found".to_string(),
+                "validation".to_string(),
+            ],
+            retryable_patterns: vec![
+                "timeout".to_string(),
+                "timed out".to_string(),
+                "connection refused".to_string(),
+                "connection reset".to_string(),
+                "503".to_string(),
+                "502".to_string(),
+                "504".to_string(),
+                "unavailable".to_string(),
+            ],
+        }
+    }
+}
+
+/// Circuit breaker tripping and cooldown thresholds.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone)]
+struct CircuitBreaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+}
+
+/// A user-installed classification rule, checked before the built-in
+/// pattern matcher. Modeled on reqwest-retry's `RetryableStrategy`: rules
+/// are tried in registration order and the first whose `pattern` matches
+/// wins, deferring to the next rule (and ultimately to `classify_error`'s
+/// built-in patterns) when it doesn't apply.
+#[derive(Debug, Clone)]
+pub struct ErrorRule {
+    pub pattern: String,
+    pub category: ErrorCategory,
+}
+
+static RETRY_CONFIG: RwLock<Option<RetryConfig>> = RwLock::new(None);
+static CIRCUIT_CONFIG: RwLock<Option<CircuitBreakerConfig>> = RwLock::new(None);
+static CIRCUIT_BREAKERS: RwLock<Option<HashMap<String, CircuitBreaker>>> = RwLock::new(None);
+static ERROR_RULES: RwLock<Vec<ErrorRule>> = RwLock::new(Vec::new());
+/// Previous sleep duration per seed, for `BackoffPolicy::DecorrelatedJitter`.
+static DECORRELATED_STATE: RwLock<Option<HashMap<u64, u64>>> = RwLock::new(None);
+
+/// Guards `ERROR_RULES` across tests in this crate, since `cargo test` runs
+/// tests from every module in parallel by default. Any test (in this file
+/// or elsewhere, e.g. `dlq`) that calls `add_error_rule`/`clear_error_rules`
+/// must hold this for its duration, mirroring `CONFIG_TEST_LOCK` below.
+#[cfg(test)]
+pub(crate) static ERROR_RULES_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Install just the backoff shape (base, cap, policy) without touching the
+/// rest of the retry policy. Useful for callers (e.g. the WIT `retry`
+/// interface) that don't go through `configure`/YAML.
+pub fn configure_backoff(base_ms: u64, cap_ms: u64, policy: BackoffPolicy) {
+    let mut config = RETRY_CONFIG.write().unwrap();
+    let mut current = config.clone().unwrap_or_default();
+    current.base_backoff_ms = base_ms;
+    current.max_backoff_ms = cap_ms;
+    current.backoff_policy = policy;
+    *config = Some(current);
+}
+
+/// Install the retry policy used by `classify_error`, `should_retry`, and
+/// `calculate_backoff`.
+pub fn configure(config: RetryConfig) {
+    *RETRY_CONFIG.write().unwrap() = Some(config);
+}
+
+/// Install the circuit breaker thresholds used by `record_destination_outcome`
+/// and `is_circuit_open`.
+pub fn configure_circuit_breaker(config: CircuitBreakerConfig) {
+    *CIRCUIT_CONFIG.write().unwrap() = Some(config);
+}
+
+fn get_config() -> RetryConfig {
+    RETRY_CONFIG.read().unwrap().clone().unwrap_or_default()
+}
+
+fn get_circuit_config() -> CircuitBreakerConfig {
+    CIRCUIT_CONFIG.read().unwrap().clone().unwrap_or_default()
+}
+
+fn with_breakers<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut HashMap<String, CircuitBreaker>) -> R,
+{
+    let mut guard = CIRCUIT_BREAKERS.write().unwrap();
+    let map = guard.get_or_insert_with(HashMap::new);
+    f(map)
+}
+
+/// Install a custom classification rule ahead of the built-in matcher. See
+/// `ErrorRule` for how rules are ordered and resolved.
+pub fn add_error_rule(pattern: impl Into<String>, category: ErrorCategory) {
+    let mut rules = ERROR_RULES.write().unwrap();
+    rules.push(ErrorRule {
+        pattern: pattern.into(),
+        category,
+    });
+}
+
+/// Remove all custom classification rules, reverting to the built-in
+/// matcher alone.
+pub fn clear_error_rules() {
+    ERROR_RULES.write().unwrap().clear();
+}
+
+/// Classify an error message against the configured retryable and
+/// non-retryable patterns. Custom rules installed via `add_error_rule` are
+/// tried first, in registration order; the first one whose pattern matches
+/// wins. Non-retryable patterns are checked first, so an explicit permanent
+/// match always wins over an overlapping transient one.
+pub fn classify_error(error: &str) -> ErrorCategory {
+    let lower = error.to_lowercase();
+
+    let rules = ERROR_RULES.read().unwrap();
+    if let Some(rule) = rules.iter().find(|r| lower.contains(&r.pattern.to_lowercase())) {
+        return rule.category;
+    }
+    drop(rules);
+
+    let config = get_config();
+    if config.non_retryable_patterns.iter().any(|p| lower.contains(&p.to_lowercase())) {
+        return ErrorCategory::Permanent;
+    }
+    if config.retryable_patterns.iter().any(|p| lower.contains(&p.to_lowercase())) {
+        return ErrorCategory::Transient;
+    }
+    ErrorCategory::Unknown
+}
+
+/// Determine if an error should be retried, based on its classification and
+/// the configured max-attempts cap. Unclassified (`Unknown`) errors are
+/// retried, erring toward availability, since most unclassified failures in
+/// practice turn out to be transient.
+pub fn should_retry(error: &str, attempt: u32) -> bool {
+    let config = get_config();
+    if attempt >= config.max_attempts {
+        return false;
+    }
+    match classify_error(error) {
+        ErrorCategory::Permanent => false,
+        ErrorCategory::Transient | ErrorCategory::Unknown => true,
+    }
 }
 
-/// Calculate backoff duration in milliseconds (noop for PoC)
-/// Always returns 0 - no backoff logic implemented yet
-pub fn calculate_backoff(_attempt: u32) -> u64 {
-    0
+/// `should_retry`, additionally short-circuited to `false` while the
+/// circuit breaker for `destination` is open.
+pub fn should_retry_for_destination(destination: &str, error: &str, attempt: u32) -> bool {
+    if is_circuit_open(destination) {
+        return false;
+    }
+    should_retry(error, attempt)
+}
+
+/// A simple splitmix64-based generator seeded from the system clock and an
+/// atomic counter. Not cryptographically secure, which is fine for backoff
+/// jitter.
+fn next_random_u64() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+        ^ COUNTER.fetch_add(1, Ordering::Relaxed).wrapping_mul(0x9E3779B97F4A7C15);
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Calculate backoff duration in milliseconds using the configured
+/// `BackoffPolicy`, drawing jitter from the ambient RNG. A fleet retrying
+/// the same failure doesn't all wake up at once under `FullJitter` (the
+/// default) or `DecorrelatedJitter`.
+pub fn calculate_backoff(attempt: u32) -> u64 {
+    calculate_backoff_with_seed(attempt, next_random_u64())
+}
+
+/// `calculate_backoff`, but deterministic: jitter is derived from `seed`
+/// instead of the ambient RNG, so the same `(attempt, seed)` always
+/// produces the same result. Needed because a WASM component can't rely on
+/// ambient randomness; callers should derive `seed` from something stable
+/// per retry sequence (e.g. the event key), since `DecorrelatedJitter`
+/// remembers the previous sleep per seed.
+pub fn calculate_backoff_with_seed(attempt: u32, seed: u64) -> u64 {
+    let config = get_config();
+    let base = config.base_backoff_ms;
+    let cap = config.max_backoff_ms;
+    let rng = seeded_random_u64(seed, attempt);
+
+    let sleep = match config.backoff_policy {
+        BackoffPolicy::Fixed => base,
+        BackoffPolicy::Exponential => {
+            (base as f64 * config.backoff_factor.powi(attempt as i32)).max(0.0) as u64
+        }
+        BackoffPolicy::FullJitter => {
+            let computed = (base as f64 * config.backoff_factor.powi(attempt as i32)).max(0.0) as u64;
+            if computed == 0 {
+                0
+            } else {
+                rng % (computed.min(cap) + 1)
+            }
+        }
+        BackoffPolicy::DecorrelatedJitter => {
+            let prev_sleep = if attempt == 0 {
+                base
+            } else {
+                DECORRELATED_STATE
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .and_then(|state| state.get(&seed).copied())
+                    .unwrap_or(base)
+            };
+            let upper = (prev_sleep.saturating_mul(3)).max(base);
+            let sleep = base + rng % (upper - base + 1);
+            let sleep = sleep.min(cap);
+            DECORRELATED_STATE
+                .write()
+                .unwrap()
+                .get_or_insert_with(HashMap::new)
+                .insert(seed, sleep);
+            sleep
+        }
+    };
+
+    sleep.min(cap)
+}
+
+/// A deterministic splitmix64-style hash of `(seed, attempt)`, used by
+/// `calculate_backoff_with_seed` so jitter is reproducible without ambient
+/// randomness.
+fn seeded_random_u64(seed: u64, attempt: u32) -> u64 {
+    let mut z = seed
+        .wrapping_add(0x9E3779B97F4A7C15)
+        .wrapping_add((attempt as u64).wrapping_mul(0xBF58476D1CE4E5B9));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Evaluate the full retry policy for one attempt, given an already
+/// classified error. Used by hosts/WIT callers that classify once and then
+/// need should-retry, backoff, and dead-letter decisions together.
+///
+/// `seed` is forwarded to `calculate_backoff_with_seed` so the backoff is
+/// deterministic; callers that don't care about reproducibility can derive
+/// it however they like (e.g. hash of the event id).
+pub fn get_retry_decision(category: ErrorCategory, attempt: u32, max_attempts: u32, seed: u64) -> RetryDecision {
+    let should = match category {
+        ErrorCategory::Permanent => false,
+        ErrorCategory::Transient | ErrorCategory::Unknown => attempt < max_attempts,
+    };
+    let backoff_ms = if should { calculate_backoff_with_seed(attempt, seed) } else { 0 };
+    if should {
+        telemetry::record_retry_attempt(attempt, backoff_ms);
+    }
+
+    RetryDecision {
+        should_retry: should,
+        backoff_ms,
+        send_to_dlq: !should,
+    }
+}
+
+/// Report the outcome of a delivery attempt to `destination`'s circuit
+/// breaker: a success closes it, a failure counts toward the trip
+/// threshold and, once reached, opens it for `cooldown`.
+pub fn record_destination_outcome(destination: &str, success: bool) {
+    let config = get_circuit_config();
+    with_breakers(|map| {
+        let breaker = map.entry(destination.to_string()).or_insert_with(CircuitBreaker::new);
+        if success {
+            breaker.state = CircuitState::Closed;
+            breaker.consecutive_failures = 0;
+            breaker.opened_at = None;
+        } else {
+            breaker.consecutive_failures += 1;
+            if breaker.consecutive_failures >= config.failure_threshold {
+                breaker.state = CircuitState::Open;
+                breaker.opened_at = Some(Instant::now());
+            }
+        }
+    });
+}
+
+/// Whether `destination`'s circuit breaker is currently open. Transitions
+/// an open breaker to half-open once its cooldown has elapsed. A half-open
+/// breaker reports closed on every call until the next recorded outcome
+/// closes it (success) or re-opens it (failure).
+pub fn is_circuit_open(destination: &str) -> bool {
+    let config = get_circuit_config();
+    with_breakers(|map| match map.get_mut(destination) {
+        Some(breaker) => match breaker.state {
+            CircuitState::Open => match breaker.opened_at {
+                Some(opened_at) if opened_at.elapsed() >= config.cooldown => {
+                    breaker.state = CircuitState::HalfOpen;
+                    false
+                }
+                _ => true,
+            },
+            CircuitState::HalfOpen | CircuitState::Closed => false,
+        },
+        None => false,
+    })
+}
+
+// YAML configuration structures
+
+#[derive(Debug, Deserialize)]
+struct RetryConfigFile {
+    retry: RetryConfigYaml,
 }
 
+#[derive(Debug, Deserialize)]
+struct RetryConfigYaml {
+    #[serde(default = "default_base_backoff_ms")]
+    base_backoff_ms: u64,
+    #[serde(default = "default_backoff_factor")]
+    backoff_factor: f64,
+    #[serde(default = "default_max_backoff_ms")]
+    max_backoff_ms: u64,
+    #[serde(default = "default_max_attempts")]
+    max_attempts: u32,
+    #[serde(default = "default_backoff_policy")]
+    backoff_policy: String,
+    #[serde(default)]
+    retryable_patterns: Option<Vec<String>>,
+    #[serde(default)]
+    non_retryable_patterns: Option<Vec<String>>,
+    #[serde(default)]
+    circuit_breaker: Option<CircuitBreakerConfigYaml>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CircuitBreakerConfigYaml {
+    #[serde(default = "default_failure_threshold")]
+    failure_threshold: u32,
+    #[serde(default = "default_cooldown_seconds")]
+    cooldown_seconds: u64,
+}
+
+fn default_base_backoff_ms() -> u64 {
+    RetryConfig::default().base_backoff_ms
+}
+fn default_backoff_factor() -> f64 {
+    RetryConfig::default().backoff_factor
+}
+fn default_max_backoff_ms() -> u64 {
+    RetryConfig::default().max_backoff_ms
+}
+fn default_max_attempts() -> u32 {
+    RetryConfig::default().max_attempts
+}
+fn default_backoff_policy() -> String {
+    "full_jitter".to_string()
+}
+fn parse_backoff_policy(policy: &str) -> BackoffPolicy {
+    match policy.to_lowercase().as_str() {
+        "fixed" => BackoffPolicy::Fixed,
+        "exponential" => BackoffPolicy::Exponential,
+        "decorrelated_jitter" | "decorrelated-jitter" => BackoffPolicy::DecorrelatedJitter,
+        _ => BackoffPolicy::FullJitter,
+    }
+}
+fn default_failure_threshold() -> u32 {
+    CircuitBreakerConfig::default().failure_threshold
+}
+fn default_cooldown_seconds() -> u64 {
+    CircuitBreakerConfig::default().cooldown.as_secs()
+}
+
+/// Load a retry policy (and optional circuit breaker thresholds) from a
+/// YAML file.
+pub fn load_retry_config(file_path: &str) -> Result<(), String> {
+    let yaml_content =
+        fs::read_to_string(file_path).map_err(|e| format!("Failed to read retry config file: {}", e))?;
+
+    let parsed: RetryConfigFile =
+        serde_yaml::from_str(&yaml_content).map_err(|e| format!("Failed to parse retry config YAML: {}", e))?;
+
+    let defaults = RetryConfig::default();
+    configure(RetryConfig {
+        base_backoff_ms: parsed.retry.base_backoff_ms,
+        backoff_factor: parsed.retry.backoff_factor,
+        max_backoff_ms: parsed.retry.max_backoff_ms,
+        max_attempts: parsed.retry.max_attempts,
+        backoff_policy: parse_backoff_policy(&parsed.retry.backoff_policy),
+        retryable_patterns: parsed.retry.retryable_patterns.unwrap_or(defaults.retryable_patterns),
+        non_retryable_patterns: parsed
+            .retry
+            .non_retryable_patterns
+            .unwrap_or(defaults.non_retryable_patterns),
+    });
+
+    if let Some(cb) = parsed.retry.circuit_breaker {
+        configure_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: cb.failure_threshold,
+            cooldown: Duration::from_secs(cb.cooldown_seconds),
+        });
+    }
+
+    Ok(())
+}
+
+// FFI exports
+
 /// FFI-compatible function to check if error should be retried
 /// Returns 1 for true, 0 for false
 #[no_mangle]
@@ -42,6 +550,135 @@ pub extern "C" fn eda_calculate_backoff(attempt: u32) -> u64 {
     calculate_backoff(attempt)
 }
 
+/// FFI-compatible function to calculate a deterministic backoff duration
+/// from a caller-supplied seed/nonce. Returns backoff duration in
+/// milliseconds.
+#[no_mangle]
+pub extern "C" fn eda_calculate_backoff_with_seed(attempt: u32, seed: u64) -> u64 {
+    calculate_backoff_with_seed(attempt, seed)
+}
+
+/// FFI-compatible function to set the backoff shape (base, cap, policy).
+/// `policy` is `0` = fixed, `1` = exponential, `2` = full jitter, `3` =
+/// decorrelated jitter.
+#[no_mangle]
+pub extern "C" fn eda_configure_backoff(base_ms: u64, cap_ms: u64, policy: u32) {
+    let policy = match policy {
+        0 => BackoffPolicy::Fixed,
+        1 => BackoffPolicy::Exponential,
+        3 => BackoffPolicy::DecorrelatedJitter,
+        _ => BackoffPolicy::FullJitter,
+    };
+    configure_backoff(base_ms, cap_ms, policy);
+}
+
+/// FFI-compatible function to load a retry policy from a YAML file
+#[no_mangle]
+pub extern "C" fn eda_load_retry_config(file_path: *const c_char) -> bool {
+    if file_path.is_null() {
+        return false;
+    }
+    let path_str = unsafe {
+        match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return false,
+        }
+    };
+    load_retry_config(path_str).is_ok()
+}
+
+/// FFI-compatible function to report a delivery outcome to a destination's
+/// circuit breaker.
+#[no_mangle]
+pub extern "C" fn eda_record_destination_outcome(destination: *const c_char, success: bool) {
+    if destination.is_null() {
+        return;
+    }
+    let destination_str = unsafe {
+        match CStr::from_ptr(destination).to_str() {
+            Ok(s) => s,
+            Err(_) => return,
+        }
+    };
+    record_destination_outcome(destination_str, success);
+}
+
+/// FFI-compatible function to check whether a destination's circuit
+/// breaker is open.
+#[no_mangle]
+pub extern "C" fn eda_is_circuit_open(destination: *const c_char) -> bool {
+    if destination.is_null() {
+        return false;
+    }
+    let destination_str = unsafe {
+        match CStr::from_ptr(destination).to_str() {
+            Ok(s) => s,
+            Err(_) => return false,
+        }
+    };
+    is_circuit_open(destination_str)
+}
+
+/// FFI-compatible function to check if an error should be retried against a
+/// specific destination, honoring its circuit breaker.
+#[no_mangle]
+pub extern "C" fn eda_should_retry_for_destination(
+    destination: *const c_char,
+    error: *const c_char,
+    attempt: u32,
+) -> i32 {
+    if destination.is_null() || error.is_null() {
+        return 0;
+    }
+    let destination_str = unsafe {
+        match CStr::from_ptr(destination).to_str() {
+            Ok(s) => s,
+            Err(_) => return 0,
+        }
+    };
+    let error_str = unsafe {
+        match CStr::from_ptr(error).to_str() {
+            Ok(s) => s,
+            Err(_) => return 0,
+        }
+    };
+
+    if should_retry_for_destination(destination_str, error_str, attempt) {
+        1
+    } else {
+        0
+    }
+}
+
+/// FFI-compatible function to install a custom error classification rule.
+/// `category` is `0` for transient, `1` for permanent, anything else for
+/// unknown.
+#[no_mangle]
+pub extern "C" fn eda_add_error_rule(pattern: *const c_char, category: u32) -> bool {
+    if pattern.is_null() {
+        return false;
+    }
+    let pattern_str = unsafe {
+        match CStr::from_ptr(pattern).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return false,
+        }
+    };
+    let category = match category {
+        0 => ErrorCategory::Transient,
+        1 => ErrorCategory::Permanent,
+        _ => ErrorCategory::Unknown,
+    };
+    add_error_rule(pattern_str, category);
+    true
+}
+
+/// FFI-compatible function to clear all custom error classification rules.
+#[no_mangle]
+pub extern "C" fn eda_clear_error_rules() {
+    clear_error_rules();
+}
+
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
@@ -56,3 +693,219 @@ pub fn should_retry_wasm(error: &str, attempt: u32) -> bool {
 pub fn calculate_backoff_wasm(attempt: u32) -> u64 {
     calculate_backoff(attempt)
 }
+
+/// Deterministic variant of `calculate_backoff_wasm`, since a WASM
+/// component can't rely on ambient RNG: same `(attempt, seed)` always
+/// produces the same result.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn calculate_backoff_with_seed_wasm(attempt: u32, seed: u64) -> u64 {
+    calculate_backoff_with_seed(attempt, seed)
+}
+
+/// `policy` is `0` = fixed, `1` = exponential, `2` = full jitter, `3` =
+/// decorrelated jitter.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn configure_backoff_wasm(base_ms: u64, cap_ms: u64, policy: u32) {
+    let policy = match policy {
+        0 => BackoffPolicy::Fixed,
+        1 => BackoffPolicy::Exponential,
+        3 => BackoffPolicy::DecorrelatedJitter,
+        _ => BackoffPolicy::FullJitter,
+    };
+    configure_backoff(base_ms, cap_ms, policy);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn should_retry_for_destination_wasm(destination: &str, error: &str, attempt: u32) -> bool {
+    should_retry_for_destination(destination, error, attempt)
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn record_destination_outcome_wasm(destination: &str, success: bool) {
+    record_destination_outcome(destination, success);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn is_circuit_open_wasm(destination: &str) -> bool {
+    is_circuit_open(destination)
+}
+
+/// `category` is `0` for transient, `1` for permanent, anything else for unknown.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn add_error_rule_wasm(pattern: &str, category: u32) {
+    let category = match category {
+        0 => ErrorCategory::Transient,
+        1 => ErrorCategory::Permanent,
+        _ => ErrorCategory::Unknown,
+    };
+    add_error_rule(pattern, category);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn clear_error_rules_wasm() {
+    clear_error_rules();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// `RETRY_CONFIG` is process-global, so any test that calls `configure`
+    /// must hold this for its duration or `cargo test`'s parallel threads
+    /// can interleave configs and corrupt each other's assertions.
+    static CONFIG_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_classify_error() {
+        assert_eq!(classify_error("request timed out"), ErrorCategory::Transient);
+        assert_eq!(classify_error("503 Service Unavailable"), ErrorCategory::Transient);
+        assert_eq!(classify_error("400 Bad Request: invalid payload"), ErrorCategory::Permanent);
+        assert_eq!(classify_error("some unrelated failure"), ErrorCategory::Unknown);
+    }
+
+    #[test]
+    fn test_should_retry_respects_classification_and_max_attempts() {
+        let _guard = CONFIG_TEST_LOCK.lock().unwrap();
+        configure(RetryConfig {
+            max_attempts: 3,
+            ..RetryConfig::default()
+        });
+
+        assert!(should_retry("connection refused", 0));
+        assert!(should_retry("connection refused", 2));
+        assert!(!should_retry("connection refused", 3));
+        assert!(!should_retry("404 not found", 0));
+    }
+
+    #[test]
+    fn test_calculate_backoff_bounds() {
+        let _guard = CONFIG_TEST_LOCK.lock().unwrap();
+        configure(RetryConfig {
+            base_backoff_ms: 100,
+            backoff_factor: 2.0,
+            max_backoff_ms: 1_000,
+            ..RetryConfig::default()
+        });
+
+        for attempt in 0..10 {
+            let backoff = calculate_backoff(attempt);
+            assert!(backoff <= 1_000, "attempt {} produced {}ms", attempt, backoff);
+        }
+    }
+
+    #[test]
+    fn test_get_retry_decision() {
+        let decision = get_retry_decision(ErrorCategory::Transient, 1, 5, 42);
+        assert!(decision.should_retry);
+        assert!(!decision.send_to_dlq);
+
+        let decision = get_retry_decision(ErrorCategory::Permanent, 0, 5, 42);
+        assert!(!decision.should_retry);
+        assert!(decision.send_to_dlq);
+        assert_eq!(decision.backoff_ms, 0);
+
+        let decision = get_retry_decision(ErrorCategory::Transient, 5, 5, 42);
+        assert!(!decision.should_retry);
+        assert!(decision.send_to_dlq);
+    }
+
+    #[test]
+    fn test_calculate_backoff_with_seed_is_deterministic() {
+        let _guard = CONFIG_TEST_LOCK.lock().unwrap();
+        configure(RetryConfig {
+            base_backoff_ms: 100,
+            backoff_factor: 2.0,
+            max_backoff_ms: 10_000,
+            backoff_policy: BackoffPolicy::FullJitter,
+            ..RetryConfig::default()
+        });
+
+        let a = calculate_backoff_with_seed(3, 777);
+        let b = calculate_backoff_with_seed(3, 777);
+        assert_eq!(a, b);
+
+        let c = calculate_backoff_with_seed(3, 778);
+        assert_ne!(a, c, "different seeds should (almost always) diverge");
+    }
+
+    #[test]
+    fn test_fixed_backoff_policy_ignores_attempt_and_jitter() {
+        let _guard = CONFIG_TEST_LOCK.lock().unwrap();
+        configure(RetryConfig {
+            base_backoff_ms: 250,
+            max_backoff_ms: 10_000,
+            backoff_policy: BackoffPolicy::Fixed,
+            ..RetryConfig::default()
+        });
+
+        for attempt in 0..5 {
+            assert_eq!(calculate_backoff_with_seed(attempt, 1), 250);
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_stays_within_bounds() {
+        let _guard = CONFIG_TEST_LOCK.lock().unwrap();
+        configure(RetryConfig {
+            base_backoff_ms: 100,
+            max_backoff_ms: 5_000,
+            backoff_policy: BackoffPolicy::DecorrelatedJitter,
+            ..RetryConfig::default()
+        });
+
+        let seed = 9001;
+        for attempt in 0..10 {
+            let backoff = calculate_backoff_with_seed(attempt, seed);
+            assert!(backoff >= 100 && backoff <= 5_000, "attempt {} produced {}ms", attempt, backoff);
+        }
+    }
+
+    #[test]
+    fn test_custom_error_rules_take_precedence_over_builtin_patterns() {
+        let _guard = ERROR_RULES_TEST_LOCK.lock().unwrap();
+        clear_error_rules();
+        add_error_rule("429", ErrorCategory::Transient);
+        add_error_rule("broker unreachable", ErrorCategory::Permanent);
+
+        assert_eq!(classify_error("429 Too Many Requests"), ErrorCategory::Transient);
+        assert_eq!(classify_error("broker unreachable"), ErrorCategory::Permanent);
+        // Falls back to the built-in matcher once no custom rule applies.
+        assert_eq!(classify_error("connection refused"), ErrorCategory::Transient);
+
+        clear_error_rules();
+        assert_eq!(classify_error("429 Too Many Requests"), ErrorCategory::Unknown);
+    }
+
+    #[test]
+    fn test_circuit_breaker_trips_and_resets() {
+        configure_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown: Duration::from_millis(10),
+        });
+
+        let destination = "test-circuit-destination";
+        assert!(!is_circuit_open(destination));
+
+        record_destination_outcome(destination, false);
+        record_destination_outcome(destination, false);
+        assert!(!is_circuit_open(destination));
+
+        record_destination_outcome(destination, false);
+        assert!(is_circuit_open(destination));
+        assert!(!should_retry_for_destination(destination, "connection refused", 0));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!is_circuit_open(destination));
+
+        record_destination_outcome(destination, true);
+        assert!(!is_circuit_open(destination));
+    }
+}
@@ -1,19 +1,13 @@
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::os::raw::c_char;
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 use std::fs;
 use cloudevents::{Event, AttributesReader};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-// TODO: Add CESQL (CloudEvents SQL) support for advanced filtering
-// The CloudEvents Rust SDK (v0.9) doesn't yet support CESQL filtering.
-// Consider:
-// 1. Checking if there's an open issue in cloudevents/sdk-rust for CESQL support
-// 2. If not, create an issue requesting CESQL implementation
-// 3. For now, we implement basic filter dialects (exact, prefix, suffix, all, any, not)
-// 4. CESQL would enable complex queries like: "type LIKE 'com.example.%' AND EXISTS priority"
-// Reference: https://github.com/cloudevents/spec/blob/main/cesql/spec.md
+use crate::{cesql, content_filter, schema_registry, telemetry};
 
 /// Destination type for output event routing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,14 +16,31 @@ pub enum DestinationType {
     RabbitMQ,
     Http,
     Discard,
+    /// Events that failed Schema Registry validation are routed here
+    /// instead of to the rule they would otherwise have matched.
+    DeadLetter,
+    /// MQTT broker. Relevant `options`: `topic`, `qos`.
+    Mqtt,
+    /// Redis stream. Relevant `options`: `stream`, `maxlen`.
+    Redis,
+    /// Relational store. Relevant `options`: `table`, `upsert_key`.
+    Sql,
 }
 
-/// Output destination with type, target, and optional cluster
-#[derive(Debug, Clone)]
+/// Output destination with type, target, cluster, and sink-specific options
+/// (e.g. MQTT `topic`/`qos`, Redis `stream`/`maxlen`, SQL `table`/`upsert_key`).
+#[derive(Debug, Clone, Default)]
 pub struct OutputDestination {
     pub dest_type: DestinationType,
     pub target: String,
     pub cluster: Option<String>,
+    pub options: HashMap<String, String>,
+}
+
+impl Default for DestinationType {
+    fn default() -> Self {
+        DestinationType::Kafka
+    }
 }
 
 /// Filter expression for routing rules (JSON-serialized CloudEvents Subscriptions API format)
@@ -41,11 +52,173 @@ pub struct RoutingRule {
     pub name: String,
     pub filter: FilterExpression,
     pub destination: OutputDestination,
+    /// Whether an event matching this rule's filter is validated against the
+    /// Schema Registry first; one that fails goes to the dead-letter
+    /// destination instead of to later rules. Ignored if no registry is
+    /// configured.
+    pub validate_schema: bool,
 }
 
 /// Global routing state
 static ROUTING_RULES: RwLock<Vec<RoutingRule>> = RwLock::new(Vec::new());
 static DEFAULT_DESTINATION: RwLock<Option<OutputDestination>> = RwLock::new(None);
+static DEAD_LETTER_DESTINATION: RwLock<Option<OutputDestination>> = RwLock::new(None);
+
+/// Liveness state of a replica within a destination's replica pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplicaHealth {
+    Healthy,
+    Degraded,
+}
+
+/// One target within a destination's replica pool, with a selection
+/// weight and current liveness state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replica {
+    pub target: String,
+    pub weight: u32,
+    pub health: ReplicaHealth,
+}
+
+/// A replica is temporarily evicted (marked `Degraded`) after this many
+/// consecutive reported failures, and restored on its next success.
+const CONSECUTIVE_FAILURES_TO_EVICT: u32 = 3;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ReplicaOutcome {
+    consecutive_failures: u32,
+}
+
+/// Replica pools, keyed by the logical destination name (an
+/// `OutputDestination.target` that a rule or the default resolves to).
+static REPLICA_POOLS: RwLock<Option<HashMap<String, Vec<Replica>>>> = RwLock::new(None);
+/// Consecutive-outcome tracking per `(destination_key, target)`, backing
+/// the automatic eviction/restoration in `record_replica_outcome`.
+static REPLICA_OUTCOMES: RwLock<Option<HashMap<(String, String), ReplicaOutcome>>> = RwLock::new(None);
+
+/// Register the replica pool for a logical destination name. Replaces any
+/// previously registered pool for the same name.
+pub fn register_replica_pool(destination_key: impl Into<String>, replicas: Vec<Replica>) {
+    REPLICA_POOLS.write().unwrap().get_or_insert_with(HashMap::new).insert(destination_key.into(), replicas);
+}
+
+/// Manually mark a replica healthy or degraded (e.g. from an external
+/// health check), independent of the automatic outcome-based eviction.
+pub fn set_replica_health(destination_key: &str, target: &str, health: ReplicaHealth) {
+    if let Some(pool) = REPLICA_POOLS.write().unwrap().as_mut().and_then(|pools| pools.get_mut(destination_key)) {
+        for replica in pool.iter_mut() {
+            if replica.target == target {
+                replica.health = health;
+            }
+        }
+    }
+}
+
+/// Report the outcome of the last delivery attempt to a replica, fed by
+/// the host after it actually performs the I/O. After
+/// `CONSECUTIVE_FAILURES_TO_EVICT` consecutive failures the replica is
+/// marked `Degraded`; a single success restores it to `Healthy`.
+pub fn record_replica_outcome(destination_key: &str, target: &str, success: bool) {
+    let key = (destination_key.to_string(), target.to_string());
+    let health = {
+        let mut guard = REPLICA_OUTCOMES.write().unwrap();
+        let outcome = guard.get_or_insert_with(HashMap::new).entry(key).or_default();
+        if success {
+            outcome.consecutive_failures = 0;
+            ReplicaHealth::Healthy
+        } else {
+            outcome.consecutive_failures += 1;
+            if outcome.consecutive_failures >= CONSECUTIVE_FAILURES_TO_EVICT {
+                ReplicaHealth::Degraded
+            } else {
+                return;
+            }
+        }
+    };
+
+    set_replica_health(destination_key, target, health);
+}
+
+/// FNV-1a hash used for deterministic, seed-free sticky replica selection.
+fn deterministic_hash(key: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in key.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Deterministically pick a healthy, positively-weighted replica from
+/// `destination_key`'s pool. The same `sticky_key` always selects the same
+/// replica for a given pool state, so routing stays sticky for a given
+/// event/partition key. Returns `None` if no pool is registered, or no
+/// replica in it is currently healthy.
+fn select_replica(destination_key: &str, sticky_key: &str) -> Option<String> {
+    let pools = REPLICA_POOLS.read().unwrap();
+    let pool = pools.as_ref()?.get(destination_key)?;
+
+    let healthy: Vec<&Replica> = pool.iter().filter(|r| r.health == ReplicaHealth::Healthy && r.weight > 0).collect();
+    let total_weight: u64 = healthy.iter().map(|r| r.weight as u64).sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut point = deterministic_hash(sticky_key) % total_weight;
+    for replica in &healthy {
+        let weight = replica.weight as u64;
+        if point < weight {
+            return Some(replica.target.clone());
+        }
+        point -= weight;
+    }
+    None
+}
+
+/// If a replica pool is registered under `dest.target`, resolve it to one
+/// of its healthy replicas (sticky on `sticky_key`); otherwise leave the
+/// destination unchanged.
+fn apply_replica_selection(mut dest: OutputDestination, sticky_key: &str) -> OutputDestination {
+    if let Some(replica_target) = select_replica(&dest.target, sticky_key) {
+        dest.target = replica_target;
+    }
+    dest
+}
+
+/// Identifier of a registered inbound handler.
+pub type HandlerId = String;
+
+/// An inbound subscription: a filter paired with the handler it dispatches
+/// to, and a priority for deterministic ordering when several subscriptions
+/// match the same event (lower priority values are returned first).
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub filter: FilterExpression,
+    pub handler_id: HandlerId,
+    pub priority: i32,
+}
+
+static SUBSCRIPTIONS: RwLock<Vec<Subscription>> = RwLock::new(Vec::new());
+
+/// Compiled `content_filter` expressions, keyed by their source text, so a
+/// rule's filter is parsed once (at `add_routing_rule` time) rather than
+/// on every event it's evaluated against.
+static CONTENT_FILTER_CACHE: RwLock<Option<HashMap<String, Arc<content_filter::CompiledExpr>>>> =
+    RwLock::new(None);
+
+fn compiled_content_filter(expr: &str) -> Result<Arc<content_filter::CompiledExpr>, String> {
+    if let Some(cached) = CONTENT_FILTER_CACHE.read().unwrap().as_ref().and_then(|c| c.get(expr)) {
+        return Ok(cached.clone());
+    }
+    let compiled = Arc::new(content_filter::compile(expr)?);
+    CONTENT_FILTER_CACHE
+        .write()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(expr.to_string(), compiled.clone());
+    Ok(compiled)
+}
 
 /// Evaluate a filter expression against a CloudEvent
 ///
@@ -56,16 +229,19 @@ static DEFAULT_DESTINATION: RwLock<Option<OutputDestination>> = RwLock::new(None
 /// - all: all nested filters must match
 /// - any: at least one nested filter must match
 /// - not: negates nested filter
-fn evaluate_filter(event: &Event, filter_json: &str) -> bool {
+/// - cesql: a CloudEvents SQL expression string (see the `cesql` module)
+/// - expr: a content-based filter expression over the full event JSON,
+///   including `data` (see the `content_filter` module)
+fn evaluate_filter(event: &Event, root: &Value, filter_json: &str) -> bool {
     let filter: Value = match serde_json::from_str(filter_json) {
         Ok(f) => f,
         Err(_) => return false,
     };
 
-    evaluate_filter_value(event, &filter)
+    evaluate_filter_value(event, root, &filter)
 }
 
-fn evaluate_filter_value(event: &Event, filter: &Value) -> bool {
+fn evaluate_filter_value(event: &Event, root: &Value, filter: &Value) -> bool {
     if let Some(obj) = filter.as_object() {
         // Check for filter dialect keywords
         if let Some(exact) = obj.get("exact") {
@@ -78,13 +254,19 @@ fn evaluate_filter_value(event: &Event, filter: &Value) -> bool {
             return evaluate_suffix(event, suffix);
         }
         if let Some(all) = obj.get("all") {
-            return evaluate_all(event, all);
+            return evaluate_all(event, root, all);
         }
         if let Some(any) = obj.get("any") {
-            return evaluate_any(event, any);
+            return evaluate_any(event, root, any);
         }
         if let Some(not) = obj.get("not") {
-            return !evaluate_filter_value(event, not);
+            return !evaluate_filter_value(event, root, not);
+        }
+        if let Some(Value::String(expr)) = obj.get("cesql") {
+            return cesql::evaluate(event, expr).unwrap_or(false);
+        }
+        if let Some(Value::String(expr)) = obj.get("expr") {
+            return compiled_content_filter(expr).map(|f| f.evaluate(root)).unwrap_or(false);
         }
     }
     false
@@ -131,10 +313,10 @@ fn evaluate_suffix(event: &Event, suffix: &Value) -> bool {
     false
 }
 
-fn evaluate_all(event: &Event, all: &Value) -> bool {
+fn evaluate_all(event: &Event, root: &Value, all: &Value) -> bool {
     if let Some(arr) = all.as_array() {
         for filter in arr {
-            if !evaluate_filter_value(event, filter) {
+            if !evaluate_filter_value(event, root, filter) {
                 return false;
             }
         }
@@ -143,10 +325,10 @@ fn evaluate_all(event: &Event, all: &Value) -> bool {
     false
 }
 
-fn evaluate_any(event: &Event, any: &Value) -> bool {
+fn evaluate_any(event: &Event, root: &Value, any: &Value) -> bool {
     if let Some(arr) = any.as_array() {
         for filter in arr {
-            if evaluate_filter_value(event, filter) {
+            if evaluate_filter_value(event, root, filter) {
                 return true;
             }
         }
@@ -155,7 +337,7 @@ fn evaluate_any(event: &Event, any: &Value) -> bool {
     false
 }
 
-fn get_event_attribute(event: &Event, key: &str) -> String {
+pub(crate) fn get_event_attribute(event: &Event, key: &str) -> String {
     match key {
         "type" => event.ty().to_string(),
         "source" => event.source().to_string(),
@@ -172,6 +354,18 @@ fn get_event_attribute(event: &Event, key: &str) -> String {
     }
 }
 
+/// Whether `key` is present on the event, as opposed to merely defaulting to empty.
+///
+/// Used by the `cesql` dialect's `EXISTS` predicate, where absence must be
+/// distinguishable from an attribute that is present but empty.
+pub(crate) fn event_attribute_exists(event: &Event, key: &str) -> bool {
+    match key {
+        "type" | "source" | "id" => true,
+        "subject" => event.subject().is_some(),
+        _ => event.extension(key).is_some(),
+    }
+}
+
 /// Get the output destination for an event based on routing rules
 ///
 /// Routes output events from handlers to their destinations based on configured rules.
@@ -188,24 +382,52 @@ pub fn get_output_destination(event_json: &str) -> OutputDestination {
         Ok(e) => e,
         Err(_) => return get_default_destination(),
     };
-    
+
+    let root: Value = serde_json::from_str(event_json).unwrap_or(Value::Null);
+    let data: Value = match &root {
+        Value::Object(obj) => obj.get("data").cloned().unwrap_or(Value::Null),
+        _ => Value::Null,
+    };
+
     let rules = ROUTING_RULES.read().unwrap();
-    
+
     // Evaluate each rule's filter against the event
     for rule in rules.iter() {
-        if evaluate_filter(&event, &rule.filter) {
-            return rule.destination.clone();
+        if !evaluate_filter(&event, &root, &rule.filter) {
+            continue;
+        }
+
+        if rule.validate_schema && schema_registry::is_configured() {
+            if let Err(_err) = schema_registry::validate_event(&event, &data) {
+                let subject = schema_registry::resolve_subject(&event);
+                telemetry::record_schema_validation_failure(&subject);
+                return apply_replica_selection(get_dead_letter_destination(), event.id());
+            }
         }
+
+        return apply_replica_selection(rule.destination.clone(), event.id());
     }
-    
+
     // No matching rule, return default destination
-    get_default_destination()
+    apply_replica_selection(get_default_destination(), event.id())
 }
 
-/// Add a routing rule
-pub fn add_routing_rule(rule: RoutingRule) {
+/// Add a routing rule.
+///
+/// If `rule.filter` carries the `expr` content-filter dialect, the
+/// expression is compiled and cached now so per-event routing doesn't
+/// reparse it. A malformed expression is rejected here, with the parse
+/// error returned, rather than silently never matching.
+pub fn add_routing_rule(rule: RoutingRule) -> Result<(), String> {
+    if let Ok(Value::Object(obj)) = serde_json::from_str::<Value>(&rule.filter) {
+        if let Some(Value::String(expr)) = obj.get("expr") {
+            compiled_content_filter(expr)?;
+        }
+    }
+
     let mut rules = ROUTING_RULES.write().unwrap();
     rules.push(rule);
+    Ok(())
 }
 
 /// Clear all routing rules
@@ -223,6 +445,7 @@ pub fn get_default_destination() -> OutputDestination {
             dest_type: DestinationType::Kafka,
             target: "events".to_string(),
             cluster: Some("default".to_string()),
+            options: HashMap::new(),
         }
     })
 }
@@ -233,16 +456,83 @@ pub fn set_default_destination(dest: OutputDestination) {
     *default = Some(dest);
 }
 
+/// Get the destination used for events that fail schema validation
+pub fn get_dead_letter_destination() -> OutputDestination {
+    let dead_letter = DEAD_LETTER_DESTINATION.read().unwrap();
+    dead_letter.clone().unwrap_or_else(|| OutputDestination {
+        dest_type: DestinationType::DeadLetter,
+        target: "dead-letter".to_string(),
+        cluster: None,
+        options: HashMap::new(),
+    })
+}
+
+/// Set the destination used for events that fail schema validation
+pub fn set_dead_letter_destination(dest: OutputDestination) {
+    let mut dead_letter = DEAD_LETTER_DESTINATION.write().unwrap();
+    *dead_letter = Some(dest);
+}
+
+/// Register an inbound subscription, dispatching events matching `filter`
+/// to `handler_id`.
+pub fn add_subscription(filter: FilterExpression, handler_id: HandlerId, priority: i32) {
+    let mut subscriptions = SUBSCRIPTIONS.write().unwrap();
+    subscriptions.push(Subscription {
+        filter,
+        handler_id,
+        priority,
+    });
+}
+
+/// Clear all inbound subscriptions.
+pub fn clear_subscriptions() {
+    let mut subscriptions = SUBSCRIPTIONS.write().unwrap();
+    subscriptions.clear();
+}
+
+/// Evaluate every registered subscription's filter against an incoming
+/// CloudEvent, reusing the same filter engine `get_output_destination` uses
+/// for output routing, and return the ids of every handler whose filter
+/// matched (fan-out). Results are ordered by ascending `priority`, then by
+/// registration order, for deterministic dispatch.
+pub fn match_handlers(event_json: &str) -> Vec<HandlerId> {
+    let event: Event = match serde_json::from_str(event_json) {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+
+    let root: Value = serde_json::from_str(event_json).unwrap_or(Value::Null);
+    let subscriptions = SUBSCRIPTIONS.read().unwrap();
+    let mut matches: Vec<(usize, &Subscription)> = subscriptions
+        .iter()
+        .enumerate()
+        .filter(|(_, sub)| evaluate_filter(&event, &root, &sub.filter))
+        .collect();
+
+    matches.sort_by_key(|(index, sub)| (sub.priority, *index));
+    matches.into_iter().map(|(_, sub)| sub.handler_id.clone()).collect()
+}
+
 // YAML configuration structures
 #[derive(Debug, Deserialize)]
 struct RoutingConfig {
     routing: RoutingConfigInner,
+    subscriptions: Option<Vec<SubscriptionConfig>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionConfig {
+    filter: Value,
+    handler_id: String,
+    #[serde(default)]
+    priority: i32,
 }
 
 #[derive(Debug, Deserialize)]
 struct RoutingConfigInner {
     default: Option<DestinationConfig>,
     rules: Option<Vec<RuleConfig>>,
+    schema_registry: Option<SchemaRegistryConfigYaml>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -250,6 +540,12 @@ struct RuleConfig {
     name: String,
     filter: Value,
     destination: DestinationConfig,
+    #[serde(default = "default_true")]
+    validate_schema: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize)]
@@ -259,6 +555,20 @@ struct DestinationConfig {
     #[serde(default)]
     target: String,
     cluster: Option<String>,
+    #[serde(default)]
+    options: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaRegistryConfigYaml {
+    endpoint: String,
+    #[serde(default = "default_cache_ttl_seconds")]
+    cache_ttl_seconds: u64,
+    dead_letter: Option<DestinationConfig>,
+}
+
+fn default_cache_ttl_seconds() -> u64 {
+    300
 }
 
 /// Load routing configuration from a YAML file
@@ -266,11 +576,11 @@ pub fn load_routing_config(file_path: &str) -> Result<(), String> {
     // Read the YAML file
     let yaml_content = fs::read_to_string(file_path)
         .map_err(|e| format!("Failed to read routing config file: {}", e))?;
-    
+
     // Parse YAML
     let config: RoutingConfig = serde_yaml::from_str(&yaml_content)
         .map_err(|e| format!("Failed to parse routing config YAML: {}", e))?;
-    
+
     // Set default destination if provided
     if let Some(default_config) = config.routing.default {
         let dest_type = parse_destination_type(&default_config.dest_type);
@@ -278,10 +588,27 @@ pub fn load_routing_config(file_path: &str) -> Result<(), String> {
             dest_type,
             target: default_config.target,
             cluster: default_config.cluster,
+            options: default_config.options,
         };
         set_default_destination(default_dest);
     }
-    
+
+    // Configure the Schema Registry and its dead-letter destination, if provided
+    if let Some(registry_config) = config.routing.schema_registry {
+        schema_registry::configure(
+            registry_config.endpoint,
+            std::time::Duration::from_secs(registry_config.cache_ttl_seconds),
+        );
+        if let Some(dead_letter_config) = registry_config.dead_letter {
+            set_dead_letter_destination(OutputDestination {
+                dest_type: parse_destination_type(&dead_letter_config.dest_type),
+                target: dead_letter_config.target,
+                cluster: dead_letter_config.cluster,
+                options: dead_letter_config.options,
+            });
+        }
+    }
+
     // Add routing rules if provided
     if let Some(rules) = config.routing.rules {
         for rule_config in rules {
@@ -290,18 +617,27 @@ pub fn load_routing_config(file_path: &str) -> Result<(), String> {
                 dest_type,
                 target: rule_config.destination.target,
                 cluster: rule_config.destination.cluster,
+                options: rule_config.destination.options,
             };
-            
+
             let rule = RoutingRule {
                 name: rule_config.name,
                 filter: rule_config.filter.to_string(),
                 destination,
+                validate_schema: rule_config.validate_schema,
             };
-            
-            add_routing_rule(rule);
+
+            add_routing_rule(rule)?;
         }
     }
-    
+
+    // Add inbound subscriptions if provided
+    if let Some(subscriptions) = config.subscriptions {
+        for sub_config in subscriptions {
+            add_subscription(sub_config.filter.to_string(), sub_config.handler_id, sub_config.priority);
+        }
+    }
+
     Ok(())
 }
 
@@ -311,18 +647,24 @@ fn parse_destination_type(type_str: &str) -> DestinationType {
         "rabbitmq" | "amqp" => DestinationType::RabbitMQ,
         "http" | "https" => DestinationType::Http,
         "discard" => DestinationType::Discard,
+        "deadletter" | "dead-letter" | "dlq" => DestinationType::DeadLetter,
+        "mqtt" => DestinationType::Mqtt,
+        "redis" => DestinationType::Redis,
+        "sql" => DestinationType::Sql,
         _ => DestinationType::Kafka,
     }
 }
 
 // FFI exports
 
-/// FFI-compatible output destination structure
+/// FFI-compatible output destination structure. `options` is a JSON object
+/// (`{"key":"value",...}`) serialized to a C string, or `NULL` if empty.
 #[repr(C)]
 pub struct COutputDestination {
     pub dest_type: u32,
     pub target: *mut c_char,
     pub cluster: *mut c_char,
+    pub options: *mut c_char,
 }
 
 use std::ffi::CString;
@@ -347,17 +689,29 @@ pub extern "C" fn eda_get_output_destination(event_json: *const c_char) -> *mut
         DestinationType::RabbitMQ => 1,
         DestinationType::Http => 2,
         DestinationType::Discard => 3,
+        DestinationType::DeadLetter => 4,
+        DestinationType::Mqtt => 5,
+        DestinationType::Redis => 6,
+        DestinationType::Sql => 7,
     };
-    
+
     let target = CString::new(dest.target).unwrap().into_raw();
     let cluster = dest.cluster
         .map(|c| CString::new(c).unwrap().into_raw())
         .unwrap_or(std::ptr::null_mut());
-    
+    let options = if dest.options.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        CString::new(serde_json::to_string(&dest.options).unwrap_or_default())
+            .unwrap_or_default()
+            .into_raw()
+    };
+
     Box::into_raw(Box::new(COutputDestination {
         dest_type,
         target,
         cluster,
+        options,
     }))
 }
 
@@ -366,7 +720,7 @@ pub extern "C" fn eda_free_output_destination(dest: *mut COutputDestination) {
     if dest.is_null() {
         return;
     }
-    
+
     unsafe {
         let dest_box = Box::from_raw(dest);
         if !dest_box.target.is_null() {
@@ -375,6 +729,9 @@ pub extern "C" fn eda_free_output_destination(dest: *mut COutputDestination) {
         if !dest_box.cluster.is_null() {
             let _ = CString::from_raw(dest_box.cluster);
         }
+        if !dest_box.options.is_null() {
+            let _ = CString::from_raw(dest_box.options);
+        }
     }
 }
 
@@ -395,6 +752,113 @@ pub extern "C" fn eda_load_routing_config(file_path: *const c_char) -> bool {
     load_routing_config(path_str).is_ok()
 }
 
+/// Register an inbound subscription via FFI.
+#[no_mangle]
+pub extern "C" fn eda_add_subscription(filter: *const c_char, handler_id: *const c_char, priority: i32) -> bool {
+    if filter.is_null() || handler_id.is_null() {
+        return false;
+    }
+
+    let filter_str = unsafe {
+        match CStr::from_ptr(filter).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return false,
+        }
+    };
+    let handler_id_str = unsafe {
+        match CStr::from_ptr(handler_id).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return false,
+        }
+    };
+
+    add_subscription(filter_str, handler_id_str, priority);
+    true
+}
+
+/// Look up which registered handlers should receive `event_json` via FFI.
+/// Returns a JSON array of handler ids as a C string, to be freed with
+/// `eda_free_string`.
+#[no_mangle]
+pub extern "C" fn eda_match_handlers(event_json: *const c_char) -> *mut c_char {
+    if event_json.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let json_str = unsafe {
+        match CStr::from_ptr(event_json).to_str() {
+            Ok(s) => s,
+            Err(_) => return std::ptr::null_mut(),
+        }
+    };
+
+    let handler_ids = match_handlers(json_str);
+    let serialized = serde_json::to_string(&handler_ids).unwrap_or_else(|_| "[]".to_string());
+
+    CString::new(serialized).map(CString::into_raw).unwrap_or(std::ptr::null_mut())
+}
+
+/// Register a destination's replica pool via FFI. `replicas_json` is a
+/// JSON array of `{"target":...,"weight":...,"health":"healthy"|"degraded"}`.
+#[no_mangle]
+pub extern "C" fn eda_register_replica_pool(destination_key: *const c_char, replicas_json: *const c_char) -> bool {
+    if destination_key.is_null() || replicas_json.is_null() {
+        return false;
+    }
+
+    let (key, json) = unsafe {
+        match (CStr::from_ptr(destination_key).to_str(), CStr::from_ptr(replicas_json).to_str()) {
+            (Ok(k), Ok(j)) => (k.to_string(), j.to_string()),
+            _ => return false,
+        }
+    };
+
+    match serde_json::from_str::<Vec<Replica>>(&json) {
+        Ok(replicas) => {
+            register_replica_pool(key, replicas);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Mark a replica healthy (`health` = 0) or degraded (`health` = 1) via FFI.
+#[no_mangle]
+pub extern "C" fn eda_set_replica_health(destination_key: *const c_char, target: *const c_char, health: u32) -> bool {
+    if destination_key.is_null() || target.is_null() {
+        return false;
+    }
+
+    let (key, target) = unsafe {
+        match (CStr::from_ptr(destination_key).to_str(), CStr::from_ptr(target).to_str()) {
+            (Ok(k), Ok(t)) => (k.to_string(), t.to_string()),
+            _ => return false,
+        }
+    };
+
+    let health = if health == 0 { ReplicaHealth::Healthy } else { ReplicaHealth::Degraded };
+    set_replica_health(&key, &target, health);
+    true
+}
+
+/// Report the outcome of the last delivery attempt to a replica via FFI.
+#[no_mangle]
+pub extern "C" fn eda_record_replica_outcome(destination_key: *const c_char, target: *const c_char, success: bool) -> bool {
+    if destination_key.is_null() || target.is_null() {
+        return false;
+    }
+
+    let (key, target) = unsafe {
+        match (CStr::from_ptr(destination_key).to_str(), CStr::from_ptr(target).to_str()) {
+            (Ok(k), Ok(t)) => (k.to_string(), t.to_string()),
+            _ => return false,
+        }
+    };
+
+    record_replica_outcome(&key, &target, success);
+    true
+}
+
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
@@ -407,9 +871,62 @@ pub fn get_output_destination_wasm(event_json: &str) -> u32 {
         DestinationType::RabbitMQ => 1,
         DestinationType::Http => 2,
         DestinationType::Discard => 3,
+        DestinationType::DeadLetter => 4,
+        DestinationType::Mqtt => 5,
+        DestinationType::Redis => 6,
+        DestinationType::Sql => 7,
     }
 }
 
+/// The sink-specific options (MQTT `topic`/`qos`, Redis `stream`/`maxlen`,
+/// SQL `table`/`upsert_key`, ...) for an event's output destination, as a
+/// JSON object string.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn get_output_destination_options_wasm(event_json: &str) -> String {
+    let dest = get_output_destination(event_json);
+    serde_json::to_string(&dest.options).unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn add_subscription_wasm(filter: &str, handler_id: &str, priority: i32) {
+    add_subscription(filter.to_string(), handler_id.to_string(), priority);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn match_handlers_wasm(event_json: &str) -> Vec<String> {
+    match_handlers(event_json)
+}
+
+/// Register a destination's replica pool. `replicas_json` is a JSON array
+/// of `{"target":...,"weight":...,"health":"healthy"|"degraded"}`.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn register_replica_pool_wasm(destination_key: &str, replicas_json: &str) -> bool {
+    match serde_json::from_str::<Vec<Replica>>(replicas_json) {
+        Ok(replicas) => {
+            register_replica_pool(destination_key.to_string(), replicas);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_replica_health_wasm(destination_key: &str, target: &str, degraded: bool) {
+    let health = if degraded { ReplicaHealth::Degraded } else { ReplicaHealth::Healthy };
+    set_replica_health(destination_key, target, health);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn record_replica_outcome_wasm(destination_key: &str, target: &str, success: bool) {
+    record_replica_outcome(destination_key, target, success);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -422,6 +939,7 @@ mod tests {
             dest_type: DestinationType::Kafka,
             target: "events".to_string(),
             cluster: Some("default".to_string()),
+            options: HashMap::new(),
         };
         set_default_destination(default);
     }
@@ -442,6 +960,7 @@ mod tests {
             dest_type: DestinationType::Http,
             target: "https://example.com/webhook".to_string(),
             cluster: None,
+            options: HashMap::new(),
         };
         set_default_destination(custom_dest);
         
@@ -462,10 +981,12 @@ mod tests {
                 dest_type: DestinationType::Kafka,
                 target: "test-topic".to_string(),
                 cluster: Some("test-cluster".to_string()),
+                options: HashMap::new(),
             },
+            validate_schema: false,
         };
         
-        add_routing_rule(rule);
+        add_routing_rule(rule).unwrap();
         
         let event_json = r#"{"specversion":"1.0","type":"com.example.test","source":"test","id":"1"}"#;
         let result = get_output_destination(event_json);
@@ -484,10 +1005,12 @@ mod tests {
                 dest_type: DestinationType::Kafka,
                 target: "example-events".to_string(),
                 cluster: Some("default".to_string()),
+                options: HashMap::new(),
             },
+            validate_schema: false,
         };
         
-        add_routing_rule(rule);
+        add_routing_rule(rule).unwrap();
         
         let event_json = r#"{"specversion":"1.0","type":"com.example.order.created","source":"test","id":"1"}"#;
         let result = get_output_destination(event_json);
@@ -506,10 +1029,12 @@ mod tests {
                 dest_type: DestinationType::Kafka,
                 target: "created-events".to_string(),
                 cluster: Some("default".to_string()),
+                options: HashMap::new(),
             },
+            validate_schema: false,
         };
         
-        add_routing_rule(rule);
+        add_routing_rule(rule).unwrap();
         
         let event_json = r#"{"specversion":"1.0","type":"order.created","source":"test","id":"1"}"#;
         let result = get_output_destination(event_json);
@@ -517,10 +1042,106 @@ mod tests {
         assert_eq!(result.target, "created-events");
     }
 
+    #[test]
+    fn test_cesql_filter_match() {
+        reset_routing_state();
+
+        let rule = RoutingRule {
+            name: "cesql-rule".to_string(),
+            filter: r#"{"cesql":"type LIKE 'com.example.%' AND EXISTS priority"}"#.to_string(),
+            destination: OutputDestination {
+                dest_type: DestinationType::Kafka,
+                target: "priority-events".to_string(),
+                cluster: Some("default".to_string()),
+                options: HashMap::new(),
+            },
+            validate_schema: false,
+        };
+
+        add_routing_rule(rule).unwrap();
+
+        let event_json = r#"{"specversion":"1.0","type":"com.example.order.created","source":"test","id":"1","priority":"high"}"#;
+        let result = get_output_destination(event_json);
+        assert_eq!(result.target, "priority-events");
+
+        let no_priority_json = r#"{"specversion":"1.0","type":"com.example.order.created","source":"test","id":"2"}"#;
+        let result = get_output_destination(no_priority_json);
+        assert_eq!(result.target, "events");
+    }
+
+    #[test]
+    fn test_dead_letter_destination_defaults_and_override() {
+        let default = get_dead_letter_destination();
+        assert_eq!(default.dest_type, DestinationType::DeadLetter);
+        assert_eq!(default.target, "dead-letter");
+
+        set_dead_letter_destination(OutputDestination {
+            dest_type: DestinationType::Kafka,
+            target: "dlq-topic".to_string(),
+            cluster: Some("default".to_string()),
+            options: HashMap::new(),
+        });
+        let overridden = get_dead_letter_destination();
+        assert_eq!(overridden.dest_type, DestinationType::Kafka);
+        assert_eq!(overridden.target, "dlq-topic");
+    }
+
+    #[test]
+    fn test_validating_rule_ignores_unconfigured_schema_registry() {
+        reset_routing_state();
+
+        let rule = RoutingRule {
+            name: "validation-without-registry".to_string(),
+            filter: r#"{"exact":{"type":"com.example.test"}}"#.to_string(),
+            destination: OutputDestination {
+                dest_type: DestinationType::Kafka,
+                target: "test-topic".to_string(),
+                cluster: Some("default".to_string()),
+                options: HashMap::new(),
+            },
+            validate_schema: true,
+        };
+        add_routing_rule(rule).unwrap();
+
+        let event_json = r#"{"specversion":"1.0","type":"com.example.test","source":"test","id":"1"}"#;
+        let result = get_output_destination(event_json);
+        assert_eq!(result.target, "test-topic");
+    }
+
+    #[test]
+    fn test_mqtt_redis_sql_destinations_carry_options() {
+        reset_routing_state();
+
+        let mut mqtt_options = HashMap::new();
+        mqtt_options.insert("topic".to_string(), "sensors/+/temperature".to_string());
+        mqtt_options.insert("qos".to_string(), "1".to_string());
+
+        let rule = RoutingRule {
+            name: "mqtt-rule".to_string(),
+            filter: r#"{"exact":{"type":"com.example.sensor.reading"}}"#.to_string(),
+            destination: OutputDestination {
+                dest_type: DestinationType::Mqtt,
+                target: "broker.example.com:1883".to_string(),
+                cluster: None,
+                options: mqtt_options.clone(),
+            },
+            validate_schema: false,
+        };
+        add_routing_rule(rule).unwrap();
+
+        let event_json = r#"{"specversion":"1.0","type":"com.example.sensor.reading","source":"test","id":"1"}"#;
+        let result = get_output_destination(event_json);
+        assert_eq!(result.dest_type, DestinationType::Mqtt);
+        assert_eq!(result.options, mqtt_options);
+
+        assert_eq!(parse_destination_type("redis"), DestinationType::Redis);
+        assert_eq!(parse_destination_type("sql"), DestinationType::Sql);
+    }
+
     #[test]
     fn test_no_match_returns_default() {
         reset_routing_state();
-        
+
         let rule = RoutingRule {
             name: "specific-rule".to_string(),
             filter: r#"{"exact":{"type":"specific.type"}}"#.to_string(),
@@ -528,15 +1149,168 @@ mod tests {
                 dest_type: DestinationType::Http,
                 target: "http://example.com".to_string(),
                 cluster: None,
+                options: HashMap::new(),
             },
+            validate_schema: false,
         };
-        
-        add_routing_rule(rule);
-        
+
+        add_routing_rule(rule).unwrap();
+
         let event_json = r#"{"specversion":"1.0","type":"different.type","source":"test","id":"1"}"#;
         let result = get_output_destination(event_json);
         // Should return default destination
         assert_eq!(result.dest_type, DestinationType::Kafka);
         assert_eq!(result.target, "events");
     }
+
+    #[test]
+    fn test_replica_selection_is_sticky_per_event_and_skips_degraded() {
+        register_replica_pool(
+            "orders-pool".to_string(),
+            vec![
+                Replica { target: "replica-a".to_string(), weight: 1, health: ReplicaHealth::Healthy },
+                Replica { target: "replica-b".to_string(), weight: 1, health: ReplicaHealth::Degraded },
+            ],
+        );
+
+        let first = select_replica("orders-pool", "event-1");
+        let second = select_replica("orders-pool", "event-1");
+        assert_eq!(first, second);
+        assert_eq!(first, Some("replica-a".to_string()));
+    }
+
+    #[test]
+    fn test_select_replica_returns_none_when_pool_unregistered_or_all_degraded() {
+        assert_eq!(select_replica("no-such-pool", "event-1"), None);
+
+        register_replica_pool(
+            "all-degraded-pool".to_string(),
+            vec![Replica { target: "replica-a".to_string(), weight: 1, health: ReplicaHealth::Degraded }],
+        );
+        assert_eq!(select_replica("all-degraded-pool", "event-1"), None);
+    }
+
+    #[test]
+    fn test_record_replica_outcome_evicts_after_consecutive_failures_and_restores_on_success() {
+        register_replica_pool(
+            "eviction-pool".to_string(),
+            vec![Replica { target: "replica-a".to_string(), weight: 1, health: ReplicaHealth::Healthy }],
+        );
+
+        record_replica_outcome("eviction-pool", "replica-a", false);
+        record_replica_outcome("eviction-pool", "replica-a", false);
+        assert_eq!(select_replica("eviction-pool", "event-1"), Some("replica-a".to_string()));
+
+        record_replica_outcome("eviction-pool", "replica-a", false);
+        assert_eq!(select_replica("eviction-pool", "event-1"), None);
+
+        record_replica_outcome("eviction-pool", "replica-a", true);
+        assert_eq!(select_replica("eviction-pool", "event-1"), Some("replica-a".to_string()));
+    }
+
+    #[test]
+    fn test_get_output_destination_resolves_registered_replica_pool() {
+        reset_routing_state();
+        set_default_destination(OutputDestination {
+            dest_type: DestinationType::Kafka,
+            target: "replica-selection-test-pool".to_string(),
+            cluster: Some("default".to_string()),
+            options: HashMap::new(),
+        });
+        register_replica_pool(
+            "replica-selection-test-pool".to_string(),
+            vec![Replica { target: "events-replica-1".to_string(), weight: 1, health: ReplicaHealth::Healthy }],
+        );
+
+        let event_json = r#"{"specversion":"1.0","type":"anything","source":"test","id":"1"}"#;
+        let result = get_output_destination(event_json);
+        assert_eq!(result.target, "events-replica-1");
+
+        reset_routing_state();
+    }
+
+    #[test]
+    fn test_expr_filter_matches_on_data_and_top_level_fields() {
+        reset_routing_state();
+
+        let rule = RoutingRule {
+            name: "expr-rule".to_string(),
+            filter: r#"{"expr":"type == \"order.created\" && amount > 100 && region in [\"us\",\"eu\"]"}"#
+                .to_string(),
+            destination: OutputDestination {
+                dest_type: DestinationType::Kafka,
+                target: "big-orders".to_string(),
+                cluster: Some("default".to_string()),
+                options: HashMap::new(),
+            },
+            validate_schema: false,
+        };
+        add_routing_rule(rule).unwrap();
+
+        let matching = r#"{"specversion":"1.0","type":"order.created","source":"test","id":"1","amount":150,"region":"us"}"#;
+        assert_eq!(get_output_destination(matching).target, "big-orders");
+
+        let too_small = r#"{"specversion":"1.0","type":"order.created","source":"test","id":"2","amount":50,"region":"us"}"#;
+        assert_eq!(get_output_destination(too_small).target, "events");
+    }
+
+    #[test]
+    fn test_add_routing_rule_rejects_malformed_expr_filter() {
+        reset_routing_state();
+
+        let rule = RoutingRule {
+            name: "broken-expr-rule".to_string(),
+            filter: r#"{"expr":"amount >"}"#.to_string(),
+            destination: OutputDestination {
+                dest_type: DestinationType::Kafka,
+                target: "unreachable".to_string(),
+                cluster: None,
+                options: HashMap::new(),
+            },
+            validate_schema: false,
+        };
+
+        assert!(add_routing_rule(rule).is_err());
+    }
+
+    #[test]
+    fn test_match_handlers_fans_out_to_all_matching_subscriptions() {
+        clear_subscriptions();
+
+        add_subscription(r#"{"prefix":{"type":"com.example."}}"#.to_string(), "handler-a".to_string(), 0);
+        add_subscription(r#"{"exact":{"type":"com.example.order.created"}}"#.to_string(), "handler-b".to_string(), 0);
+        add_subscription(r#"{"exact":{"type":"com.example.other"}}"#.to_string(), "handler-c".to_string(), 0);
+
+        let event_json = r#"{"specversion":"1.0","type":"com.example.order.created","source":"test","id":"1"}"#;
+        let handlers = match_handlers(event_json);
+
+        assert_eq!(handlers, vec!["handler-a".to_string(), "handler-b".to_string()]);
+    }
+
+    #[test]
+    fn test_match_handlers_orders_by_priority_then_registration() {
+        clear_subscriptions();
+
+        add_subscription(r#"{"exact":{"type":"com.example.test"}}"#.to_string(), "low-priority".to_string(), 10);
+        add_subscription(r#"{"exact":{"type":"com.example.test"}}"#.to_string(), "high-priority".to_string(), 0);
+        add_subscription(r#"{"exact":{"type":"com.example.test"}}"#.to_string(), "also-high-priority".to_string(), 0);
+
+        let event_json = r#"{"specversion":"1.0","type":"com.example.test","source":"test","id":"1"}"#;
+        let handlers = match_handlers(event_json);
+
+        assert_eq!(
+            handlers,
+            vec!["high-priority".to_string(), "also-high-priority".to_string(), "low-priority".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_match_handlers_returns_empty_when_nothing_matches() {
+        clear_subscriptions();
+
+        add_subscription(r#"{"exact":{"type":"com.example.test"}}"#.to_string(), "handler-a".to_string(), 0);
+
+        let event_json = r#"{"specversion":"1.0","type":"com.example.other","source":"test","id":"1"}"#;
+        assert!(match_handlers(event_json).is_empty());
+    }
 }
@@ -0,0 +1,244 @@
+//! Schema Registry client for validating CloudEvent payloads before routing.
+//!
+//! Talks to a Confluent/Redpanda-compatible HTTP Schema Registry
+//! (`GET /subjects/{subject}/versions/latest`), caching resolved schemas by
+//! subject with a TTL so routing doesn't pay a network round-trip per
+//! event. Schemas are plain JSON Schema documents, validated with a small
+//! built-in validator covering `type`, `required`, `properties`, `items`,
+//! and `enum`.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use cloudevents::{AttributesReader, Event};
+use serde_json::Value;
+
+/// Schema Registry connection settings.
+#[derive(Debug, Clone)]
+pub struct SchemaRegistryConfig {
+    pub endpoint: String,
+    pub cache_ttl: Duration,
+}
+
+struct CachedSchema {
+    schema: Value,
+    fetched_at: Instant,
+}
+
+static REGISTRY_CONFIG: RwLock<Option<SchemaRegistryConfig>> = RwLock::new(None);
+static SCHEMA_CACHE: RwLock<Option<HashMap<String, CachedSchema>>> = RwLock::new(None);
+
+/// Configure the Schema Registry endpoint used by `validate_event`.
+pub fn configure(endpoint: impl Into<String>, cache_ttl: Duration) {
+    *REGISTRY_CONFIG.write().unwrap() = Some(SchemaRegistryConfig {
+        endpoint: endpoint.into(),
+        cache_ttl,
+    });
+    *SCHEMA_CACHE.write().unwrap() = None;
+}
+
+/// Whether a registry endpoint has been configured.
+pub fn is_configured() -> bool {
+    REGISTRY_CONFIG.read().unwrap().is_some()
+}
+
+/// Resolve the subject to validate an event's `data` against: the event's
+/// `dataschema` attribute if present, otherwise a subject derived from its
+/// `type` (`<type>-value`, matching the common Confluent TopicNameStrategy
+/// convention).
+pub fn resolve_subject(event: &Event) -> String {
+    if let Some(dataschema) = event.dataschema() {
+        return dataschema.to_string();
+    }
+    format!("{}-value", event.ty())
+}
+
+fn fetch_schema(config: &SchemaRegistryConfig, subject: &str) -> Result<Value, String> {
+    let url = format!("{}/subjects/{}/versions/latest", config.endpoint.trim_end_matches('/'), subject);
+    let response: Value = ureq::get(&url)
+        .call()
+        .map_err(|e| format!("schema registry request to {} failed: {}", url, e))?
+        .into_json()
+        .map_err(|e| format!("schema registry response from {} was not JSON: {}", url, e))?;
+
+    let schema_str = response
+        .get("schema")
+        .and_then(|s| s.as_str())
+        .ok_or_else(|| format!("schema registry response for {} missing 'schema' field", subject))?;
+
+    serde_json::from_str(schema_str).map_err(|e| format!("invalid JSON Schema for {}: {}", subject, e))
+}
+
+/// Resolve a subject's schema, serving from cache while the TTL holds.
+pub fn get_schema(subject: &str) -> Result<Value, String> {
+    let config = REGISTRY_CONFIG
+        .read()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "schema registry is not configured".to_string())?;
+
+    {
+        let cache = SCHEMA_CACHE.read().unwrap();
+        if let Some(entry) = cache.as_ref().and_then(|c| c.get(subject)) {
+            if entry.fetched_at.elapsed() < config.cache_ttl {
+                return Ok(entry.schema.clone());
+            }
+        }
+    }
+
+    let schema = fetch_schema(&config, subject)?;
+
+    let mut cache = SCHEMA_CACHE.write().unwrap();
+    cache.get_or_insert_with(HashMap::new).insert(
+        subject.to_string(),
+        CachedSchema {
+            schema: schema.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+
+    Ok(schema)
+}
+
+/// Validate `data` against the schema resolved for `event`.
+pub fn validate_event(event: &Event, data: &Value) -> Result<(), String> {
+    let subject = resolve_subject(event);
+    let schema = get_schema(&subject)?;
+    validate_value(&schema, data)
+}
+
+/// A small JSON Schema (draft-07 subset) validator: `type`, `required`,
+/// `properties`, `items`, and `enum`. Enough to catch the common payload
+/// drift this check exists for, without pulling in a full implementation.
+fn validate_value(schema: &Value, value: &Value) -> Result<(), String> {
+    let Some(schema_obj) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected_type) = schema_obj.get("type").and_then(|t| t.as_str()) {
+        if !matches_type(expected_type, value) {
+            return Err(format!("expected type '{}', found {}", expected_type, describe_type(value)));
+        }
+    }
+
+    if let Some(allowed) = schema_obj.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            return Err(format!("value {} is not one of the allowed enum values", value));
+        }
+    }
+
+    if let Some(obj) = value.as_object() {
+        if let Some(required) = schema_obj.get("required").and_then(|r| r.as_array()) {
+            for field in required {
+                if let Some(name) = field.as_str() {
+                    if !obj.contains_key(name) {
+                        return Err(format!("missing required field '{}'", name));
+                    }
+                }
+            }
+        }
+
+        if let Some(properties) = schema_obj.get("properties").and_then(|p| p.as_object()) {
+            for (name, prop_schema) in properties {
+                if let Some(prop_value) = obj.get(name) {
+                    validate_value(prop_schema, prop_value)
+                        .map_err(|e| format!("field '{}': {}", name, e))?;
+                }
+            }
+        }
+    }
+
+    if let Some(arr) = value.as_array() {
+        if let Some(item_schema) = schema_obj.get("items") {
+            for (i, item) in arr.iter().enumerate() {
+                validate_value(item_schema, item).map_err(|e| format!("item[{}]: {}", i, e))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn describe_type(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    }
+}
+
+/// Reset registry configuration and cache. Exposed for tests.
+#[cfg(test)]
+pub(crate) fn reset() {
+    *REGISTRY_CONFIG.write().unwrap() = None;
+    *SCHEMA_CACHE.write().unwrap() = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_value_required_and_type() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name", "amount"],
+            "properties": {
+                "name": {"type": "string"},
+                "amount": {"type": "number"}
+            }
+        });
+
+        assert!(validate_value(&schema, &json!({"name": "a", "amount": 1})).is_ok());
+        assert!(validate_value(&schema, &json!({"name": "a"})).is_err());
+        assert!(validate_value(&schema, &json!({"name": 1, "amount": 1})).is_err());
+    }
+
+    #[test]
+    fn test_validate_value_enum() {
+        let schema = json!({"enum": ["us", "eu"]});
+        assert!(validate_value(&schema, &json!("us")).is_ok());
+        assert!(validate_value(&schema, &json!("apac")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_subject_prefers_dataschema() {
+        use cloudevents::{EventBuilder, EventBuilderV10};
+
+        let event = EventBuilderV10::new()
+            .id("1")
+            .ty("com.example.order.created")
+            .source("test")
+            .data_schema("https://schemas.example.com/order.json".parse::<url::Url>().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(resolve_subject(&event), "https://schemas.example.com/order.json");
+    }
+
+    #[test]
+    fn test_resolve_subject_falls_back_to_type() {
+        use cloudevents::{EventBuilder, EventBuilderV10};
+
+        let event = EventBuilderV10::new().id("1").ty("com.example.order.created").source("test").build().unwrap();
+        assert_eq!(resolve_subject(&event), "com.example.order.created-value");
+    }
+}
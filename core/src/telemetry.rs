@@ -1,35 +1,196 @@
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Global event counter
 static EVENT_COUNT: AtomicU64 = AtomicU64::new(0);
 
-/// Record that an event was received (placeholder)
-pub fn record_event_received(_event_type: &str) {
-    // TODO: For production, implement comprehensive telemetry:
-    // - Increment counter with event_type label: eda_events_received_total{event_type="user.created"}
-    // - Start telemetry span for event lifecycle tracking
-    // - Record event size/payload metrics
-    // - Track consumer lag if available
+/// Histogram bucket upper bounds (seconds) for `eda_event_processing_duration_seconds`.
+const DURATION_BUCKETS_SECONDS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// A label set, stored as sorted `(name, value)` pairs so it can be used
+/// as a `HashMap` key and rendered deterministically in exposition output.
+type Labels = Vec<(String, String)>;
+
+fn labels(pairs: &[(&str, &str)]) -> Labels {
+    let mut l: Labels = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    l.sort();
+    l
+}
+
+fn render_labels(labels: &Labels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let rendered = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape_json_string(v)))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{{}}}", rendered)
+}
+
+/// Escape a string for embedding inside a JSON (or Prometheus label) string
+/// literal built via manual interpolation. Shared by `render_labels` and any
+/// hand-rolled JSON construction elsewhere in this crate, so a value
+/// containing `"` or `\` can't break out of its enclosing quotes.
+pub(crate) fn escape_json_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            bucket_counts: vec![0; DURATION_BUCKETS_SECONDS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        self.sum += value;
+        self.count += 1;
+        for (i, bound) in DURATION_BUCKETS_SECONDS.iter().enumerate() {
+            if value <= *bound {
+                self.bucket_counts[i] += 1;
+            }
+        }
+    }
+}
+
+/// A single named metric family, holding one series per distinct label set.
+#[derive(Debug, Default)]
+struct MetricFamily {
+    help: &'static str,
+    metric_type: &'static str,
+    counters: HashMap<Labels, u64>,
+    histograms: HashMap<Labels, Histogram>,
+}
+
+/// Global metric registry, keyed by metric name.
+static REGISTRY: RwLock<Option<HashMap<&'static str, MetricFamily>>> = RwLock::new(None);
+
+fn with_registry<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut HashMap<&'static str, MetricFamily>) -> R,
+{
+    let mut guard = REGISTRY.write().unwrap();
+    let map = guard.get_or_insert_with(HashMap::new);
+    f(map)
+}
+
+fn incr_counter(name: &'static str, help: &'static str, label_pairs: &[(&str, &str)], by: u64) {
+    with_registry(|reg| {
+        let family = reg.entry(name).or_insert_with(|| MetricFamily {
+            help,
+            metric_type: "counter",
+            counters: HashMap::new(),
+            histograms: HashMap::new(),
+        });
+        *family.counters.entry(labels(label_pairs)).or_insert(0) += by;
+    });
+}
+
+fn observe_histogram(name: &'static str, help: &'static str, label_pairs: &[(&str, &str)], value_seconds: f64) {
+    with_registry(|reg| {
+        let family = reg.entry(name).or_insert_with(|| MetricFamily {
+            help,
+            metric_type: "histogram",
+            counters: HashMap::new(),
+            histograms: HashMap::new(),
+        });
+        family
+            .histograms
+            .entry(labels(label_pairs))
+            .or_insert_with(Histogram::new)
+            .observe(value_seconds);
+    });
+}
+
+/// Record that an event was received.
+///
+/// Increments `eda_events_received_total{event_type}` and opens a
+/// distributed-tracing span for the event's lifecycle, continuing the
+/// trace carried in `traceparent` (W3C Trace Context) if one is given, or
+/// starting a fresh trace otherwise. The span is stashed keyed by
+/// `event_id` until `record_event_processed` closes it.
+pub fn record_event_received(event_id: &str, event_type: &str, traceparent: Option<&str>) {
     EVENT_COUNT.fetch_add(1, Ordering::Relaxed);
+    incr_counter(
+        "eda_events_received_total",
+        "Total number of CloudEvents received",
+        &[("event_type", event_type)],
+        1,
+    );
+    tracing::start_span(event_id, event_type, traceparent);
 }
 
-/// Record that an event was processed (placeholder)
-pub fn record_event_processed(_event_type: &str, _success: bool, _duration_ms: u64) {
-    // TODO: For production, implement processing metrics:
-    // - Increment counter: eda_events_processed_total{event_type, status="success|failure"}
-    // - Record histogram: eda_event_processing_duration_seconds{event_type}
-    // - Close telemetry span started in record_event_received
-    // - Record error details if success=false
+/// Record that an event finished processing.
+///
+/// Increments `eda_events_processed_total{event_type,status}` and records
+/// `eda_event_processing_duration_seconds{event_type}`. Closes the span
+/// opened in `record_event_received` for `event_id` (if any), reporting it
+/// through the configured `SpanReporter` and removing it from the span
+/// store even if processing failed.
+pub fn record_event_processed(event_id: &str, event_type: &str, success: bool, duration_ms: u64) {
     EVENT_COUNT.fetch_add(1, Ordering::Relaxed);
+    let status = if success { "success" } else { "failure" };
+    incr_counter(
+        "eda_events_processed_total",
+        "Total number of CloudEvents processed, by outcome",
+        &[("event_type", event_type), ("status", status)],
+        1,
+    );
+    observe_histogram(
+        "eda_event_processing_duration_seconds",
+        "Event processing duration in seconds",
+        &[("event_type", event_type)],
+        duration_ms as f64 / 1000.0,
+    );
+    tracing::finish_span(event_id, event_type, status, duration_ms);
+}
+
+/// Record a retry attempt, incrementing `eda_retry_attempts_total{attempt}`.
+pub fn record_retry_attempt(attempt: u32, _backoff_ms: u64) {
+    incr_counter(
+        "eda_retry_attempts_total",
+        "Total number of retry attempts, by attempt number",
+        &[("attempt", &attempt.to_string())],
+        1,
+    );
+}
+
+/// Record a schema validation failure, incrementing
+/// `eda_schema_validation_failures_total{subject}`.
+pub fn record_schema_validation_failure(subject: &str) {
+    incr_counter(
+        "eda_schema_validation_failures_total",
+        "Total number of events that failed Schema Registry validation",
+        &[("subject", subject)],
+        1,
+    );
 }
 
-/// Record a retry attempt (placeholder)
-pub fn record_retry_attempt(_attempt: u32, _backoff_ms: u64) {
-    // TODO: For production, implement retry metrics:
-    // - Increment counter: eda_retry_attempts_total{attempt}
-    // - Record backoff duration histogram
-    // - Create telemetry span for retry operation
-    // - Track retry reasons/error categories
+/// Record a dead-letter replay attempt, incrementing
+/// `eda_dlq_replays_total{status}`.
+pub fn record_dlq_replay(success: bool) {
+    let status = if success { "success" } else { "failure" };
+    incr_counter(
+        "eda_dlq_replays_total",
+        "Total number of dead-letter replay attempts, by outcome",
+        &[("status", status)],
+        1,
+    );
 }
 
 /// Get total event count
@@ -37,6 +198,368 @@ pub fn get_event_count() -> u64 {
     EVENT_COUNT.load(Ordering::Relaxed)
 }
 
+/// Get the number of records currently held in the dead-letter queue.
+pub fn get_dlq_count() -> u64 {
+    crate::dlq::len() as u64
+}
+
+/// Serialize the global metric registry into Prometheus text exposition
+/// format (`# HELP`/`# TYPE` headers, `name{labels} value` lines, with
+/// `_bucket`/`_sum`/`_count` series for histograms).
+pub fn gather_metrics() -> String {
+    let guard = REGISTRY.read().unwrap();
+    let Some(registry) = guard.as_ref() else {
+        return String::new();
+    };
+
+    let mut names: Vec<&&str> = registry.keys().collect();
+    names.sort();
+
+    let mut out = String::new();
+    for name in names {
+        let family = &registry[name];
+        out.push_str(&format!("# HELP {} {}\n", name, family.help));
+        out.push_str(&format!("# TYPE {} {}\n", name, family.metric_type));
+
+        match family.metric_type {
+            "counter" => {
+                let mut series: Vec<(&Labels, &u64)> = family.counters.iter().collect();
+                series.sort_by_key(|(l, _)| (*l).clone());
+                for (label_set, value) in series {
+                    out.push_str(&format!("{}{} {}\n", name, render_labels(label_set), value));
+                }
+            }
+            "histogram" => {
+                let mut series: Vec<(&Labels, &Histogram)> = family.histograms.iter().collect();
+                series.sort_by_key(|(l, _)| (*l).clone());
+                for (label_set, hist) in series {
+                    for (i, bound) in DURATION_BUCKETS_SECONDS.iter().enumerate() {
+                        let mut bucket_labels = label_set.clone();
+                        bucket_labels.push(("le".to_string(), bound.to_string()));
+                        bucket_labels.sort();
+                        out.push_str(&format!(
+                            "{}_bucket{} {}\n",
+                            name,
+                            render_labels(&bucket_labels),
+                            hist.bucket_counts[i]
+                        ));
+                    }
+                    let mut inf_labels = label_set.clone();
+                    inf_labels.push(("le".to_string(), "+Inf".to_string()));
+                    inf_labels.sort();
+                    out.push_str(&format!("{}_bucket{} {}\n", name, render_labels(&inf_labels), hist.count));
+                    out.push_str(&format!("{}_sum{} {}\n", name, render_labels(label_set), hist.sum));
+                    out.push_str(&format!("{}_count{} {}\n", name, render_labels(label_set), hist.count));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
+
+/// Reset the metric registry. Exposed for tests; not part of the public API.
+#[cfg(test)]
+fn reset_metrics() {
+    let mut guard = REGISTRY.write().unwrap();
+    *guard = None;
+}
+
+/// FFI-compatible function to gather metrics in Prometheus exposition format.
+/// Returns a C string that must be freed with `eda_free_string`.
+#[no_mangle]
+pub extern "C" fn eda_metrics_gather() -> *mut c_char {
+    match CString::new(gather_metrics()) {
+        Ok(c_str) => c_str.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Distributed tracing via CloudEvents Trace Context (W3C `traceparent`).
+///
+/// Propagates and emits spans around event handling, with the export path
+/// pluggable behind `SpanReporter` so hosts can ship spans wherever their
+/// observability stack expects them.
+pub mod tracing {
+    use super::*;
+
+    /// A single in-flight span's propagation context.
+    #[derive(Debug, Clone)]
+    pub struct SpanContext {
+        pub trace_id: String,
+        pub span_id: String,
+        pub parent_span_id: Option<String>,
+        pub trace_flags: u8,
+        pub trace_state: Option<String>,
+        pub event_type: String,
+    }
+
+    /// A span as handed to a `SpanReporter` once it closes.
+    #[derive(Debug, Clone)]
+    pub struct FinishedSpan {
+        pub trace_id: String,
+        pub span_id: String,
+        pub parent_span_id: Option<String>,
+        pub event_id: String,
+        pub event_type: String,
+        pub status: String,
+        pub duration_ms: u64,
+    }
+
+    /// Pluggable export target for finished spans.
+    pub trait SpanReporter: Send + Sync {
+        fn report(&self, span: &FinishedSpan);
+    }
+
+    /// Reports spans as a single-line log record on stdout.
+    pub struct LogSpanReporter;
+
+    impl SpanReporter for LogSpanReporter {
+        fn report(&self, span: &FinishedSpan) {
+            println!(
+                "trace_id={} span_id={} parent_span_id={} event_id={} event_type={} status={} duration_ms={}",
+                span.trace_id,
+                span.span_id,
+                span.parent_span_id.as_deref().unwrap_or("-"),
+                span.event_id,
+                span.event_type,
+                span.status,
+                span.duration_ms
+            );
+        }
+    }
+
+    /// Reports spans by publishing them, JSON-serialized, to a Kafka topic —
+    /// mirroring how observability agents ship trace segments over Kafka.
+    ///
+    /// This PoC implementation buffers published messages in-memory rather
+    /// than opening a real producer connection; swap in a Kafka client to
+    /// publish for real.
+    pub struct KafkaSpanReporter {
+        pub topic: String,
+        published: Mutex<Vec<String>>,
+    }
+
+    impl KafkaSpanReporter {
+        pub fn new(topic: impl Into<String>) -> Self {
+            Self {
+                topic: topic.into(),
+                published: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Messages published so far, for inspection/testing.
+        pub fn published_messages(&self) -> Vec<String> {
+            self.published.lock().unwrap().clone()
+        }
+    }
+
+    impl SpanReporter for KafkaSpanReporter {
+        fn report(&self, span: &FinishedSpan) {
+            use super::escape_json_string;
+
+            let message = format!(
+                r#"{{"trace_id":"{}","span_id":"{}","parent_span_id":{},"event_id":"{}","event_type":"{}","status":"{}","duration_ms":{}}}"#,
+                escape_json_string(&span.trace_id),
+                escape_json_string(&span.span_id),
+                span.parent_span_id
+                    .as_ref()
+                    .map(|p| format!("\"{}\"", escape_json_string(p)))
+                    .unwrap_or_else(|| "null".to_string()),
+                escape_json_string(&span.event_id),
+                escape_json_string(&span.event_type),
+                escape_json_string(&span.status),
+                span.duration_ms
+            );
+            self.published.lock().unwrap().push(message);
+        }
+    }
+
+    static SPAN_STORE: RwLock<Option<HashMap<String, SpanContext>>> = RwLock::new(None);
+    static SPAN_REPORTER: RwLock<Option<Box<dyn SpanReporter>>> = RwLock::new(None);
+
+    fn with_span_store<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut HashMap<String, SpanContext>) -> R,
+    {
+        let mut guard = SPAN_STORE.write().unwrap();
+        let map = guard.get_or_insert_with(HashMap::new);
+        f(map)
+    }
+
+    /// Install the `SpanReporter` used to export finished spans.
+    pub fn set_span_reporter(reporter: Box<dyn SpanReporter>) {
+        *SPAN_REPORTER.write().unwrap() = Some(reporter);
+    }
+
+    fn report_span(span: &FinishedSpan) {
+        let guard = SPAN_REPORTER.read().unwrap();
+        match guard.as_ref() {
+            Some(reporter) => reporter.report(span),
+            None => LogSpanReporter.report(span),
+        }
+    }
+
+    /// A simple splitmix64-based generator seeded from the system clock and
+    /// an atomic counter, used only to produce trace/span ids. Not
+    /// cryptographically secure, which is fine for correlation ids.
+    fn next_random_u64() -> u64 {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+            ^ COUNTER.fetch_add(1, Ordering::Relaxed).wrapping_mul(0x9E3779B97F4A7C15);
+        let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn generate_trace_id() -> String {
+        format!("{:016x}{:016x}", next_random_u64(), next_random_u64())
+    }
+
+    fn generate_span_id() -> String {
+        format!("{:016x}", next_random_u64())
+    }
+
+    /// Parse a W3C `traceparent` header: `00-<32hex traceid>-<16hex
+    /// spanid>-<2hex flags>`.
+    fn parse_traceparent(traceparent: &str) -> Option<(String, String, u8)> {
+        let parts: Vec<&str> = traceparent.split('-').collect();
+        if parts.len() != 4 || parts[0] != "00" {
+            return None;
+        }
+        if parts[1].len() != 32 || parts[2].len() != 16 || parts[3].len() != 2 {
+            return None;
+        }
+        let flags = u8::from_str_radix(parts[3], 16).ok()?;
+        Some((parts[1].to_string(), parts[2].to_string(), flags))
+    }
+
+    /// Open a span for `event_id`, continuing the trace in `traceparent` if
+    /// present and well-formed, or starting a fresh trace otherwise.
+    pub fn start_span(event_id: &str, event_type: &str, traceparent: Option<&str>) {
+        let (trace_id, parent_span_id, trace_flags) = match traceparent.and_then(parse_traceparent) {
+            Some((trace_id, parent_span_id, flags)) => (trace_id, Some(parent_span_id), flags),
+            None => (generate_trace_id(), None, 1),
+        };
+
+        let context = SpanContext {
+            trace_id,
+            span_id: generate_span_id(),
+            parent_span_id,
+            trace_flags,
+            trace_state: None,
+            event_type: event_type.to_string(),
+        };
+
+        with_span_store(|store| {
+            store.insert(event_id.to_string(), context);
+        });
+    }
+
+    /// Close the span for `event_id`, reporting it via the configured
+    /// `SpanReporter`. Cleans up the span-context store even if the caller
+    /// never opened a span for this id (a no-op report is skipped instead).
+    pub fn finish_span(event_id: &str, event_type: &str, status: &str, duration_ms: u64) {
+        let context = with_span_store(|store| store.remove(event_id));
+
+        let Some(context) = context else {
+            return;
+        };
+
+        report_span(&FinishedSpan {
+            trace_id: context.trace_id,
+            span_id: context.span_id,
+            parent_span_id: context.parent_span_id,
+            event_id: event_id.to_string(),
+            event_type: event_type.to_string(),
+            status: status.to_string(),
+            duration_ms,
+        });
+    }
+
+    /// The outgoing W3C `traceparent` for `event_id`'s current span, so a
+    /// downstream producer can continue the trace. `None` if no span is
+    /// open for this event.
+    pub fn outgoing_traceparent(event_id: &str) -> Option<String> {
+        with_span_store(|store| {
+            store
+                .get(event_id)
+                .map(|ctx| format!("00-{}-{}-{:02x}", ctx.trace_id, ctx.span_id, ctx.trace_flags))
+        })
+    }
+
+    /// Clear all in-flight span contexts. Exposed for tests.
+    #[cfg(test)]
+    pub(crate) fn reset() {
+        *SPAN_STORE.write().unwrap() = None;
+        *SPAN_REPORTER.write().unwrap() = None;
+    }
+}
+
+/// Configure the tracing subsystem to export spans to stdout (the default).
+#[no_mangle]
+pub extern "C" fn eda_tracing_use_log_reporter() {
+    tracing::set_span_reporter(Box::new(tracing::LogSpanReporter));
+}
+
+/// Configure the tracing subsystem to export spans to a Kafka topic.
+#[no_mangle]
+pub extern "C" fn eda_tracing_use_kafka_reporter(topic: *const c_char) -> bool {
+    if topic.is_null() {
+        return false;
+    }
+    let topic_str = unsafe {
+        match CStr::from_ptr(topic).to_str() {
+            Ok(s) => s,
+            Err(_) => return false,
+        }
+    };
+    tracing::set_span_reporter(Box::new(tracing::KafkaSpanReporter::new(topic_str)));
+    true
+}
+
+/// Fetch the outgoing W3C `traceparent` for `event_id`'s current span, so a
+/// downstream producer can inject it into the next CloudEvent it emits.
+/// Returns a C string that must be freed with `eda_free_string`, or null if
+/// no span is open for this event.
+#[no_mangle]
+pub extern "C" fn eda_tracing_get_traceparent(event_id: *const c_char) -> *mut c_char {
+    if event_id.is_null() {
+        return std::ptr::null_mut();
+    }
+    let event_id_str = unsafe {
+        match CStr::from_ptr(event_id).to_str() {
+            Ok(s) => s,
+            Err(_) => return std::ptr::null_mut(),
+        }
+    };
+
+    match tracing::outgoing_traceparent(event_id_str) {
+        Some(tp) => CString::new(tp).map(CString::into_raw).unwrap_or(std::ptr::null_mut()),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn gather_metrics_wasm() -> String {
+    gather_metrics()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn get_outgoing_traceparent_wasm(event_id: &str) -> Option<String> {
+    tracing::outgoing_traceparent(event_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,8 +567,85 @@ mod tests {
     #[test]
     fn test_event_counting() {
         let before = get_event_count();
-        record_event_received("test.event");
+        record_event_received("evt-1", "test.event", None);
         let after = get_event_count();
         assert!(after > before);
     }
+
+    #[test]
+    fn test_gather_metrics_exposition_format() {
+        reset_metrics();
+        record_event_received("evt-2", "user.created", None);
+        record_event_processed("evt-2", "user.created", true, 42);
+        record_retry_attempt(1, 100);
+
+        let output = gather_metrics();
+        assert!(output.contains("# HELP eda_events_received_total"));
+        assert!(output.contains("# TYPE eda_events_received_total counter"));
+        assert!(output.contains(r#"eda_events_received_total{event_type="user.created"} 1"#));
+        assert!(output.contains(r#"eda_events_processed_total{event_type="user.created",status="success"} 1"#));
+        assert!(output.contains("eda_event_processing_duration_seconds_bucket"));
+        assert!(output.contains("eda_event_processing_duration_seconds_sum"));
+        assert!(output.contains("eda_event_processing_duration_seconds_count"));
+        assert!(output.contains(r#"eda_retry_attempts_total{attempt="1"} 1"#));
+    }
+
+    #[test]
+    fn test_gather_metrics_histogram_buckets_are_not_double_accumulated() {
+        reset_metrics();
+        record_event_processed("evt-hist", "user.created", true, 42);
+
+        let output = gather_metrics();
+        assert!(output.contains(r#"eda_event_processing_duration_seconds_bucket{event_type="user.created",le="0.025"} 0"#));
+        assert!(output.contains(r#"eda_event_processing_duration_seconds_bucket{event_type="user.created",le="0.05"} 1"#));
+        assert!(output.contains(r#"eda_event_processing_duration_seconds_bucket{event_type="user.created",le="10"} 1"#));
+        assert!(output.contains(r#"eda_event_processing_duration_seconds_bucket{event_type="user.created",le="+Inf"} 1"#));
+        assert!(output.contains(r#"eda_event_processing_duration_seconds_count{event_type="user.created"} 1"#));
+    }
+
+    #[test]
+    fn test_trace_continues_incoming_traceparent() {
+        tracing::reset();
+        let incoming = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        tracing::start_span("evt-3", "order.created", Some(incoming));
+
+        let outgoing = tracing::outgoing_traceparent("evt-3").unwrap();
+        assert!(outgoing.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+        assert_ne!(outgoing, incoming);
+    }
+
+    #[test]
+    fn test_trace_starts_fresh_without_traceparent() {
+        tracing::reset();
+        tracing::start_span("evt-4", "order.created", None);
+        let outgoing = tracing::outgoing_traceparent("evt-4").unwrap();
+        assert_eq!(outgoing.len(), "00-xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx-xxxxxxxxxxxxxxxx-xx".len());
+    }
+
+    #[test]
+    fn test_span_cleaned_up_on_finish_even_on_failure() {
+        tracing::reset();
+        tracing::start_span("evt-5", "order.created", None);
+        assert!(tracing::outgoing_traceparent("evt-5").is_some());
+
+        tracing::finish_span("evt-5", "order.created", "failure", 12);
+        assert!(tracing::outgoing_traceparent("evt-5").is_none());
+    }
+
+    #[test]
+    fn test_kafka_span_reporter_publishes_message() {
+        tracing::reset();
+        let reporter = tracing::KafkaSpanReporter::new("eda.traces");
+        reporter.report(&tracing::FinishedSpan {
+            trace_id: "t".to_string(),
+            span_id: "s".to_string(),
+            parent_span_id: None,
+            event_id: "evt-6".to_string(),
+            event_type: "order.created".to_string(),
+            status: "success".to_string(),
+            duration_ms: 5,
+        });
+        assert_eq!(reporter.published_messages().len(), 1);
+        assert!(reporter.published_messages()[0].contains("\"event_id\":\"evt-6\""));
+    }
 }
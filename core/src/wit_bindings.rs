@@ -10,10 +10,13 @@ wit_bindgen::generate!({
 });
 
 #[cfg(target_arch = "wasm32")]
-use crate::{config, retry, routing, telemetry};
+use crate::{config, dlq, retry, routing, telemetry};
 
 #[cfg(target_arch = "wasm32")]
-use exports::eda::core::{config::Guest as ConfigGuest, retry::Guest as RetryGuest, routing::Guest as RoutingGuest, telemetry::Guest as TelemetryGuest};
+use exports::eda::core::{
+    config::Guest as ConfigGuest, dlq::Guest as DlqGuest, retry::Guest as RetryGuest, routing::Guest as RoutingGuest,
+    telemetry::Guest as TelemetryGuest,
+};
 
 #[cfg(target_arch = "wasm32")]
 use eda::core::types::*;
@@ -31,6 +34,30 @@ impl ConfigGuest for Component {
             group_id: cfg.group,
         }
     }
+
+    fn get_kafka_config_for(cluster: Option<String>) -> KafkaConfig {
+        let cfg = config::get_kafka_config_for(cluster.as_deref());
+        KafkaConfig {
+            broker: cfg.broker,
+            topic: cfg.topic,
+            group_id: cfg.group,
+        }
+    }
+
+    fn add_cluster(name: String, cfg: KafkaConfig) {
+        config::add_cluster(
+            name,
+            config::KafkaConfig {
+                broker: cfg.broker,
+                topic: cfg.topic,
+                group: cfg.group_id,
+            },
+        );
+    }
+
+    fn list_clusters() -> Vec<String> {
+        config::list_clusters()
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -47,6 +74,7 @@ impl RetryGuest for Component {
         error_category: ErrorCategory,
         attempt: u32,
         max_attempts: u32,
+        seed: u64,
     ) -> RetryDecision {
         let cat = match error_category {
             ErrorCategory::Transient => retry::ErrorCategory::Transient,
@@ -54,47 +82,112 @@ impl RetryGuest for Component {
             ErrorCategory::Unknown => retry::ErrorCategory::Unknown,
         };
 
-        let decision = retry::get_retry_decision(cat, attempt, max_attempts);
+        let decision = retry::get_retry_decision(cat, attempt, max_attempts, seed);
         RetryDecision {
             should_retry: decision.should_retry,
             backoff_ms: decision.backoff_ms,
             send_to_dlq: decision.send_to_dlq,
         }
     }
+
+    fn add_error_rule(pattern: String, category: ErrorCategory) {
+        let cat = match category {
+            ErrorCategory::Transient => retry::ErrorCategory::Transient,
+            ErrorCategory::Permanent => retry::ErrorCategory::Permanent,
+            ErrorCategory::Unknown => retry::ErrorCategory::Unknown,
+        };
+        retry::add_error_rule(pattern, cat);
+    }
+
+    fn clear_error_rules() {
+        retry::clear_error_rules();
+    }
+
+    fn configure_backoff(base_ms: u64, cap_ms: u64, policy: BackoffPolicy) {
+        let policy = match policy {
+            BackoffPolicy::Fixed => retry::BackoffPolicy::Fixed,
+            BackoffPolicy::Exponential => retry::BackoffPolicy::Exponential,
+            BackoffPolicy::FullJitter => retry::BackoffPolicy::FullJitter,
+            BackoffPolicy::DecorrelatedJitter => retry::BackoffPolicy::DecorrelatedJitter,
+        };
+        retry::configure_backoff(base_ms, cap_ms, policy);
+    }
+
+    fn calculate_backoff(attempt: u32, seed: u64) -> u64 {
+        retry::calculate_backoff_with_seed(attempt, seed)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn destination_type_to_wit(dest_type: routing::DestinationType) -> DestinationType {
+    match dest_type {
+        routing::DestinationType::Kafka => DestinationType::Kafka,
+        routing::DestinationType::RabbitMQ => DestinationType::Rabbitmq,
+        routing::DestinationType::Http => DestinationType::Http,
+        routing::DestinationType::Discard => DestinationType::Discard,
+        routing::DestinationType::DeadLetter => DestinationType::DeadLetter,
+        routing::DestinationType::Mqtt => DestinationType::Mqtt,
+        routing::DestinationType::Redis => DestinationType::Redis,
+        routing::DestinationType::Sql => DestinationType::Sql,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn destination_type_from_wit(dest_type: DestinationType) -> routing::DestinationType {
+    match dest_type {
+        DestinationType::Kafka => routing::DestinationType::Kafka,
+        DestinationType::Rabbitmq => routing::DestinationType::RabbitMQ,
+        DestinationType::Http => routing::DestinationType::Http,
+        DestinationType::Discard => routing::DestinationType::Discard,
+        DestinationType::DeadLetter => routing::DestinationType::DeadLetter,
+        DestinationType::Mqtt => routing::DestinationType::Mqtt,
+        DestinationType::Redis => routing::DestinationType::Redis,
+        DestinationType::Sql => routing::DestinationType::Sql,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn output_destination_to_wit(dest: routing::OutputDestination) -> OutputDestination {
+    OutputDestination {
+        dest_type: destination_type_to_wit(dest.dest_type),
+        target: dest.target,
+        cluster: dest.cluster,
+        options: dest.options.into_iter().collect(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn output_destination_from_wit(dest: OutputDestination) -> routing::OutputDestination {
+    routing::OutputDestination {
+        dest_type: destination_type_from_wit(dest.dest_type),
+        target: dest.target,
+        cluster: dest.cluster,
+        options: dest.options.into_iter().collect(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn replica_health_from_wit(health: ReplicaHealth) -> routing::ReplicaHealth {
+    match health {
+        ReplicaHealth::Healthy => routing::ReplicaHealth::Healthy,
+        ReplicaHealth::Degraded => routing::ReplicaHealth::Degraded,
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
 impl RoutingGuest for Component {
     fn get_output_destination(event_json: String) -> OutputDestination {
-        let dest = routing::get_output_destination(&event_json);
-        OutputDestination {
-            dest_type: match dest.dest_type {
-                routing::DestinationType::Kafka => DestinationType::Kafka,
-                routing::DestinationType::RabbitMQ => DestinationType::Rabbitmq,
-                routing::DestinationType::Http => DestinationType::Http,
-                routing::DestinationType::Discard => DestinationType::Discard,
-            },
-            target: dest.target,
-            cluster: dest.cluster,
-        }
+        output_destination_to_wit(routing::get_output_destination(&event_json))
     }
 
-    fn add_routing_rule(rule: RoutingRule) {
+    fn add_routing_rule(rule: RoutingRule) -> Result<(), String> {
         let rust_rule = routing::RoutingRule {
             name: rule.name,
             filter: rule.filter,
-            destination: routing::OutputDestination {
-                dest_type: match rule.destination.dest_type {
-                    DestinationType::Kafka => routing::DestinationType::Kafka,
-                    DestinationType::Rabbitmq => routing::DestinationType::RabbitMQ,
-                    DestinationType::Http => routing::DestinationType::Http,
-                    DestinationType::Discard => routing::DestinationType::Discard,
-                },
-                target: rule.destination.target,
-                cluster: rule.destination.cluster,
-            },
+            destination: output_destination_from_wit(rule.destination),
+            validate_schema: rule.validate_schema,
         };
-        routing::add_routing_rule(rust_rule);
+        routing::add_routing_rule(rust_rule)
     }
 
     fn clear_routing_rules() {
@@ -102,42 +195,42 @@ impl RoutingGuest for Component {
     }
 
     fn get_default_destination() -> OutputDestination {
-        let dest = routing::get_default_destination();
-        OutputDestination {
-            dest_type: match dest.dest_type {
-                routing::DestinationType::Kafka => DestinationType::Kafka,
-                routing::DestinationType::RabbitMQ => DestinationType::Rabbitmq,
-                routing::DestinationType::Http => DestinationType::Http,
-                routing::DestinationType::Discard => DestinationType::Discard,
-            },
-            target: dest.target,
-            cluster: dest.cluster,
-        }
+        output_destination_to_wit(routing::get_default_destination())
     }
 
     fn set_default_destination(dest: OutputDestination) {
-        let rust_dest = routing::OutputDestination {
-            dest_type: match dest.dest_type {
-                DestinationType::Kafka => routing::DestinationType::Kafka,
-                DestinationType::Rabbitmq => routing::DestinationType::RabbitMQ,
-                DestinationType::Http => routing::DestinationType::Http,
-                DestinationType::Discard => routing::DestinationType::Discard,
-            },
-            target: dest.target,
-            cluster: dest.cluster,
-        };
-        routing::set_default_destination(rust_dest);
+        routing::set_default_destination(output_destination_from_wit(dest));
+    }
+
+    fn register_replica_pool(destination_key: String, replicas: Vec<Replica>) {
+        let replicas = replicas
+            .into_iter()
+            .map(|r| routing::Replica {
+                target: r.target,
+                weight: r.weight,
+                health: replica_health_from_wit(r.health),
+            })
+            .collect();
+        routing::register_replica_pool(destination_key, replicas);
+    }
+
+    fn set_replica_health(destination_key: String, target: String, health: ReplicaHealth) {
+        routing::set_replica_health(&destination_key, &target, replica_health_from_wit(health));
+    }
+
+    fn record_replica_outcome(destination_key: String, target: String, success: bool) {
+        routing::record_replica_outcome(&destination_key, &target, success);
     }
 }
 
 #[cfg(target_arch = "wasm32")]
 impl TelemetryGuest for Component {
-    fn record_event_received(event_type: String) {
-        telemetry::record_event_received(&event_type);
+    fn record_event_received(event_id: String, event_type: String, traceparent: Option<String>) {
+        telemetry::record_event_received(&event_id, &event_type, traceparent.as_deref());
     }
 
-    fn record_event_processed(event_type: String, success: bool, duration_ms: u64) {
-        telemetry::record_event_processed(&event_type, success, duration_ms);
+    fn record_event_processed(event_id: String, event_type: String, success: bool, duration_ms: u64) {
+        telemetry::record_event_processed(&event_id, &event_type, success, duration_ms);
     }
 
     fn record_retry_attempt(attempt: u32, backoff_ms: u64) {
@@ -147,6 +240,70 @@ impl TelemetryGuest for Component {
     fn get_event_count() -> u64 {
         telemetry::get_event_count()
     }
+
+    fn get_dlq_count() -> u64 {
+        telemetry::get_dlq_count()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn error_category_to_wit(category: retry::ErrorCategory) -> ErrorCategory {
+    match category {
+        retry::ErrorCategory::Transient => ErrorCategory::Transient,
+        retry::ErrorCategory::Permanent => ErrorCategory::Permanent,
+        retry::ErrorCategory::Unknown => ErrorCategory::Unknown,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn error_category_from_wit(category: ErrorCategory) -> retry::ErrorCategory {
+    match category {
+        ErrorCategory::Transient => retry::ErrorCategory::Transient,
+        ErrorCategory::Permanent => retry::ErrorCategory::Permanent,
+        ErrorCategory::Unknown => retry::ErrorCategory::Unknown,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn dlq_record_to_wit(record: dlq::DlqRecord) -> DlqRecord {
+    DlqRecord {
+        event_json: record.event_json,
+        error_message: record.error_message,
+        error_category: error_category_to_wit(record.error_category),
+        attempt_count: record.attempt_count,
+        timestamp_ms: record.timestamp_ms,
+        destination_target: record.destination_target,
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl DlqGuest for Component {
+    fn dlq_push(event_json: String, error_message: String, attempt_count: u32, destination_target: String) -> u32 {
+        dlq::dlq_push(event_json, error_message, attempt_count, destination_target) as u32
+    }
+
+    fn dlq_list() -> Vec<DlqRecord> {
+        dlq::dlq_list().into_iter().map(dlq_record_to_wit).collect()
+    }
+
+    fn dlq_list_by_category(category: ErrorCategory) -> Vec<DlqRecord> {
+        dlq::dlq_list_by_category(error_category_from_wit(category))
+            .into_iter()
+            .map(dlq_record_to_wit)
+            .collect()
+    }
+
+    fn dlq_reclassify(index: u32) -> Option<ErrorCategory> {
+        dlq::dlq_reclassify(index as usize).map(error_category_to_wit)
+    }
+
+    fn dlq_replay(index: u32) -> Result<OutputDestination, String> {
+        dlq::dlq_replay(index as usize).map(output_destination_to_wit)
+    }
+
+    fn dlq_clear() {
+        dlq::dlq_clear();
+    }
 }
 
 #[cfg(target_arch = "wasm32")]